@@ -0,0 +1,64 @@
+//! Nearest city queries through the grid index against a linear scan, over
+//! a dataset the size of every Belgian village and hamlet.
+//!
+//! ```bash
+//! cargo bench --no-default-features --features core --bench spatial
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use geo_quizz::core::{CityIndex, Point};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+const VILLAGES: usize = 20_000;
+
+fn villages() -> Vec<Point> {
+    let mut rng = StdRng::seed_from_u64(7);
+    (0..VILLAGES)
+        .map(|_| {
+            Point::new(
+                rng.random_range(-375.0..375.),
+                rng.random_range(-305.0..305.),
+            )
+        })
+        .collect()
+}
+
+fn linear_nearest(points: &[Point], query: Point) -> Option<usize> {
+    (0..points.len()).min_by(|&a, &b| {
+        points[a]
+            .distance(query)
+            .total_cmp(&points[b].distance(query))
+    })
+}
+
+fn nearest(c: &mut Criterion) {
+    let points = villages();
+    let index = CityIndex::new(points.clone());
+    let queries: Vec<Point> = villages().into_iter().rev().take(100).collect();
+
+    let mut group = c.benchmark_group("nearest of 20k villages");
+    group.bench_function("linear scan", |b| {
+        b.iter(|| {
+            for &query in &queries {
+                black_box(linear_nearest(&points, query));
+            }
+        })
+    });
+    group.bench_function("grid index", |b| {
+        b.iter(|| {
+            for &query in &queries {
+                black_box(index.nearest(query));
+            }
+        })
+    });
+    group.finish();
+
+    c.bench_function("build index of 20k villages", |b| {
+        b.iter(|| CityIndex::new(black_box(points.clone())))
+    });
+}
+
+criterion_group!(benches, nearest);
+criterion_main!(benches);
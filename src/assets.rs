@@ -1,4 +1,10 @@
-use crate::types::{CityAssets, CityNameToGuess, GuessAssets, Score, ScoreText, WorldClickCatcher};
+use crate::{
+    core::{Language, UiStrings},
+    types::{
+        ActionButton, CityAssets, CityNameToGuess, ConfirmLabel, GuessAssets, OutsideWarning,
+        PauseOverlay, Score, ScoreText, StudyMenu, UiLabel, WorldClickCatcher,
+    },
+};
 use bevy::{
     color::palettes::basic::{BLACK, RED},
     prelude::*,
@@ -20,6 +26,8 @@ impl Plugin for AssetsPlugin {
                 setup_city_assets,
                 setup_texts,
                 setup_button,
+                setup_pause_overlay,
+                setup_outside_warning,
             ),
         );
     }
@@ -40,19 +48,27 @@ fn setup_city_assets(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
 ) {
     let mesh = meshes.add(Circle::new(2.5));
     let material = materials.add(Color::srgb(255., 0., 0.));
-    commands.insert_resource(CityAssets { mesh, material });
+    let font = asset_server.load(["fonts", "FiraMono-Medium.ttf"].iter().collect::<PathBuf>());
+    commands.insert_resource(CityAssets {
+        mesh,
+        material,
+        font,
+    });
 }
 
-fn setup_texts(mut commands: Commands, score: Res<Score>) {
+fn setup_texts(mut commands: Commands, score: Res<Score>, language: Res<Language>) {
+    let strings = language.strings();
     commands
         .spawn((
             // `Text` or `Text2d` are needed, and will provide default instances
             // of the following components.
-            Text::new("Find "),
+            Text::new(strings.find),
             TextColor(BLACK.into()),
+            UiLabel::Find,
         ))
         .with_child((
             // Children must be `TextSpan`, not `Text` or `Text2d`.
@@ -68,8 +84,9 @@ fn setup_texts(mut commands: Commands, score: Res<Score>) {
                 right: Val::Px(5.),
                 ..default()
             },
-            Text::new("Score: "),
+            Text::new(strings.score),
             TextColor(BLACK.into()),
+            UiLabel::Score,
         ))
         .with_child((
             // Children must be `TextSpan`, not `Text` or `Text2d`.
@@ -79,7 +96,54 @@ fn setup_texts(mut commands: Commands, score: Res<Score>) {
         ));
 }
 
-fn setup_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_pause_overlay(mut commands: Commands, language: Res<Language>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.5)),
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        PauseOverlay,
+        children![
+            (
+                Text::new(language.strings().paused),
+                TextColor(Color::WHITE),
+                UiLabel::Paused,
+            ),
+            // Filled by the study plugin
+            (Text::default(), TextColor(Color::WHITE), StudyMenu),
+        ],
+    ));
+}
+
+fn setup_outside_warning(mut commands: Commands, language: Res<Language>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            top: Val::Px(60.),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Visibility::Hidden,
+        OutsideWarning,
+        children![(
+            Text::new(language.strings().outside_country),
+            TextColor(Color::srgb(0.8, 0.1, 0.1)),
+            UiLabel::OutsideCountry,
+        )],
+    ));
+}
+
+fn setup_button(mut commands: Commands, asset_server: Res<AssetServer>, language: Res<Language>) {
     commands.spawn((
         Node {
             width: Val::Percent(100.0),
@@ -99,12 +163,13 @@ fn setup_button(mut commands: Commands, asset_server: Res<AssetServer>) {
             BackgroundColor(Color::NONE),
             WorldClickCatcher,
             FocusPolicy::Pass,
-            children![button(&asset_server)]
+            children![button(&asset_server, language.strings())]
         )],
     ));
 }
 
-fn button(asset_server: &AssetServer) -> impl Bundle + use<> {
+fn button(asset_server: &AssetServer, strings: &UiStrings) -> impl Bundle + use<> {
+    let font = asset_server.load(["fonts", "FiraMono-Medium.ttf"].iter().collect::<PathBuf>());
     (
         Node {
             position_type: PositionType::Absolute,
@@ -114,34 +179,58 @@ fn button(asset_server: &AssetServer) -> impl Bundle + use<> {
             // height: Val::Percent(100.0),
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
+            column_gap: Val::Px(10.),
             ..default()
         },
-        children![(
-            Button,
-            Node {
-                width: Val::Px(200.0),
-                height: Val::Px(75.0),
-                border: UiRect::all(Val::Px(5.0)),
-                // horizontally center child text
-                justify_content: JustifyContent::Center,
-                // vertically center child text
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BorderColor::all(Color::BLACK),
-            BorderRadius::MAX,
-            BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
-            FocusPolicy::Block,
-            children![(
-                Text::new("Confirm"),
-                TextFont {
-                    font: asset_server
-                        .load(["fonts", "FiraMono-Medium.ttf"].iter().collect::<PathBuf>()),
-                    font_size: 25.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.9, 0.9, 0.9)),
-            )]
-        )],
+        children![
+            (
+                action_button(ActionButton::Confirm, 200.),
+                children![(
+                    Text::new(strings.confirm),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 25.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    ConfirmLabel,
+                )]
+            ),
+            // Hints lower the points of the question, hence the smaller button
+            (
+                action_button(ActionButton::Hint, 120.),
+                children![(
+                    Text::new(strings.hint),
+                    TextFont {
+                        font,
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    UiLabel::Hint,
+                )]
+            ),
+        ],
+    )
+}
+
+fn action_button(action: ActionButton, width: f32) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(75.0),
+            border: UiRect::all(Val::Px(5.0)),
+            // horizontally center child text
+            justify_content: JustifyContent::Center,
+            // vertically center child text
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor::all(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+        FocusPolicy::Block,
     )
 }
@@ -0,0 +1,221 @@
+//! Tools around the city database.
+//!
+//! ```bash
+//! cargo run --bin geoquizz-data -- build export.osm --output database/belgium_cities.json
+//! cargo run --bin geoquizz-data -- validate database/belgium_cities.json
+//! cargo run --bin geoquizz-data -- study exam.txt --name exam
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use geo_quizz::{
+    GeoError,
+    core::{
+        CityRecord, DatasetOptions, NameKeys, ProvinceMap, StudySet, load_database_with,
+        match_study_list,
+        osm::{BuildRules, build_database, parse_export},
+        parse_study_list, validate_database,
+    },
+};
+
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Build the database from a saved Overpass JSON answer or OSM XML export.
+    ///
+    /// Such an export can be saved from https://overpass-turbo.eu with, for
+    /// Belgium: `area["ISO3166-1"="BE"][admin_level=2]->.country;
+    /// node["place"~"city|town|village"](area.country); out;`
+    Build {
+        /// Overpass JSON or OSM XML file.
+        input: PathBuf,
+        #[arg(long, short, default_value = "database/belgium_cities.json")]
+        output: PathBuf,
+        /// Values of the `place` tag to keep.
+        #[arg(long, value_delimiter = ',', default_value = "city,town")]
+        place: Vec<String>,
+        /// Drop places with a smaller known population.
+        #[arg(long)]
+        min_population: Option<u64>,
+    },
+    /// Check a database for empty or duplicate names and misplaced cities.
+    ///
+    /// Exits with an error code if the database is unusable.
+    Validate {
+        /// Database built by this tool, GeoJSON points or CSV.
+        #[arg(default_value = "database/belgium_cities.json")]
+        dataset: PathBuf,
+        /// Property or column holding the city names in GeoJSON and CSV.
+        #[arg(long, default_value = "name")]
+        name_field: String,
+        /// Map the cities must be on.
+        #[arg(long, default_value = "assets/belgium_map.svg")]
+        map: PathBuf,
+    },
+    /// Save a list of city names as a study set, selectable in the game.
+    ///
+    /// Names are matched ignoring case, accents and small typos. Unmatched
+    /// and ambiguous names are reported and left out of the set.
+    Study {
+        /// Text file with one name per line, or CSV with the names first.
+        list: PathBuf,
+        /// Name of the set, defaults to the name of the list file.
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long, default_value = "database/belgium_cities.json")]
+        dataset: PathBuf,
+        /// Property or column holding the city names in GeoJSON and CSV.
+        #[arg(long, default_value = "name")]
+        name_field: String,
+        /// Folder the sets are saved in.
+        #[arg(long, default_value = "saves/study")]
+        output: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Build {
+            input,
+            output,
+            place,
+            min_population,
+        } => build(
+            input,
+            output,
+            &BuildRules {
+                places: place,
+                min_population,
+            },
+        ),
+        Command::Validate {
+            dataset,
+            name_field,
+            map,
+        } => validate(dataset, name_field, map),
+        Command::Study {
+            list,
+            name,
+            dataset,
+            name_field,
+            output,
+        } => study(list, name, dataset, name_field, output),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn build(input: PathBuf, output: PathBuf, rules: &BuildRules) -> Result<(), GeoError> {
+    let content =
+        std::fs::read_to_string(&input).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    let nodes = parse_export(&content)?;
+    println!("Found {} nodes in {}", nodes.len(), input.display());
+
+    let entries = build_database(nodes, rules);
+    println!("Kept {} places", entries.len());
+
+    let json =
+        serde_json::to_string_pretty(&entries).map_err(|e| GeoError::DataWriting(e.to_string()))?;
+    std::fs::write(&output, json + "\n").map_err(|e| GeoError::DataWriting(e.to_string()))?;
+    println!("Written to {}", output.display());
+    Ok(())
+}
+
+fn load_cities(dataset: &PathBuf, name_field: String) -> Result<Vec<CityRecord>, GeoError> {
+    let options = DatasetOptions {
+        format: None,
+        names: NameKeys {
+            default: name_field,
+            ..Default::default()
+        },
+    };
+    load_database_with(dataset, &options)
+}
+
+fn validate(dataset: PathBuf, name_field: String, map: PathBuf) -> Result<(), GeoError> {
+    let cities = load_cities(&dataset, name_field)?;
+    let provinces = ProvinceMap::load(map)?;
+    let report = validate_database(&cities, &provinces);
+    for warning in &report.warnings {
+        println!("warning: {warning}");
+    }
+    for error in &report.errors {
+        println!("error: {error}");
+    }
+    println!(
+        "{} cities, {} errors, {} warnings",
+        cities.len(),
+        report.errors.len(),
+        report.warnings.len()
+    );
+    if report.is_valid() {
+        Ok(())
+    } else {
+        Err(GeoError::DataLoading(format!(
+            "{} is not valid",
+            dataset.display()
+        )))
+    }
+}
+
+fn study(
+    list: PathBuf,
+    name: Option<String>,
+    dataset: PathBuf,
+    name_field: String,
+    output: PathBuf,
+) -> Result<(), GeoError> {
+    let content =
+        std::fs::read_to_string(&list).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    let names = parse_study_list(&content);
+    let cities = load_cities(&dataset, name_field)?;
+    let result = match_study_list(&names, &cities);
+
+    let name_of = |id| {
+        cities
+            .iter()
+            .find(|c| c.id == id)
+            .map_or("?", |c| c.names.default.as_str())
+    };
+    for name in &result.unmatched {
+        println!("not found: {name}");
+    }
+    for (name, candidates) in &result.ambiguous {
+        let candidates: Vec<String> = candidates
+            .iter()
+            .map(|&id| format!("{} ({})", name_of(id), id.0))
+            .collect();
+        println!("ambiguous: {name}, could be {}", candidates.join(", "));
+    }
+    println!("{} of {} names matched", result.matched.len(), names.len());
+    if result.matched.is_empty() {
+        return Err(GeoError::DataLoading(format!(
+            "no city of {} found",
+            list.display()
+        )));
+    }
+
+    let name = name
+        .or_else(|| Some(list.file_stem()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "study".to_string());
+    let set = StudySet {
+        name,
+        cities: result.matched,
+    };
+    set.save(&output)?;
+    println!("Saved {} to {}", set.name, output.display());
+    Ok(())
+}
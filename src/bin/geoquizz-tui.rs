@@ -0,0 +1,251 @@
+//! Text frontend over the quiz core, for terminals without a GPU.
+//!
+//! ```bash
+//! cargo run --no-default-features --features core --bin geoquizz-tui -- province
+//! ```
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use geo_quizz::core::{
+    Answer, CityRecord, Language, Point, ProvinceMap, Quiz, Region, load_database,
+};
+use rand::{
+    Rng,
+    seq::{IteratorRandom, SliceRandom},
+};
+
+/// Minimap size in characters. Terminal cells are about twice as high as
+/// wide, hence the wider grid.
+const MINIMAP_WIDTH: usize = 50;
+const MINIMAP_HEIGHT: usize = 20;
+/// Names offered in the multiple choice mode.
+const CHOICES: usize = 4;
+/// Wrong choices are drawn among this many cities around the right one.
+const NEIGHBOURS: usize = 12;
+
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    /// "Which province is Ath in?"
+    Province,
+    /// Give the French name of a Dutch one, or the other way around.
+    Translate,
+    /// Pick the city marked on the minimap among a few names.
+    Choice,
+}
+
+impl Mode {
+    fn parse(arg: &str) -> Option<Self> {
+        match arg {
+            "province" => Some(Mode::Province),
+            "translate" => Some(Mode::Translate),
+            "choice" => Some(Mode::Choice),
+            _ => None,
+        }
+    }
+}
+
+struct Question {
+    prompt: String,
+    expected: String,
+    /// Numbered options, if any.
+    choices: Vec<String>,
+    /// Language expected in the translation mode.
+    language: Language,
+}
+
+fn main() {
+    let mode = match std::env::args().nth(1) {
+        None => Mode::Province,
+        Some(arg) => Mode::parse(&arg).unwrap_or_else(|| {
+            eprintln!("Usage: geoquizz-tui [province|translate|choice]");
+            std::process::exit(2);
+        }),
+    };
+
+    let dataset: PathBuf = ["database", "belgium_cities.json"].iter().collect();
+    let map: PathBuf = ["assets", "belgium_map.svg"].iter().collect();
+    let mut cities = load_database(&dataset).unwrap_or_else(|e| {
+        eprintln!("{}: {e}", dataset.display());
+        std::process::exit(1);
+    });
+    let provinces = ProvinceMap::load(&map).unwrap_or_else(|e| {
+        eprintln!("{}: {e}", map.display());
+        std::process::exit(1);
+    });
+    if let Mode::Translate = mode {
+        cities.retain(|c| matches!((&c.names.fr, &c.names.nl), (Some(fr), Some(nl)) if fr != nl));
+    }
+    let mut quiz = Quiz::new(cities).with_provinces(provinces);
+
+    let mut lines = io::stdin().lock().lines();
+    println!("GeoQuizz — type your answer, or 'q' to quit.\n");
+
+    loop {
+        while let Some(city) = quiz.next_question().cloned() {
+            let question = ask(mode, &quiz, &city);
+            print!("{} ", question.prompt);
+            io::stdout().flush().ok();
+            let Some(Ok(line)) = lines.next() else {
+                return;
+            };
+            let line = line.trim();
+            if line.eq_ignore_ascii_case("q") {
+                return;
+            }
+
+            let answer = to_answer(mode, &quiz, &question, line);
+            let result = quiz.submit(&answer).expect("A question is pending");
+            if result.points > 0 {
+                println!("Correct! +{}", result.points);
+            } else {
+                println!("Wrong, it was {}.", question.expected);
+            }
+            let score = quiz.score();
+            println!("Score: {}/{}\n", score.total, score.max);
+        }
+
+        let score = quiz.score();
+        print!(
+            "Round over: {}/{}. Play again? [Y/n] ",
+            score.total, score.max
+        );
+        io::stdout().flush().ok();
+        match lines.next() {
+            Some(Ok(line)) if !line.trim().eq_ignore_ascii_case("n") => {
+                if let Err(e) = quiz.start_round() {
+                    eprintln!("{e}");
+                    return;
+                }
+            }
+            _ => return,
+        }
+    }
+}
+
+/// Print the minimap and options of a question, and build its prompt.
+fn ask(mode: Mode, quiz: &Quiz, city: &CityRecord) -> Question {
+    let mut rng = rand::rng();
+    match mode {
+        Mode::Province => {
+            println!("{}", minimap(quiz.provinces(), None));
+            let choices: Vec<String> = quiz
+                .provinces()
+                .provinces
+                .iter()
+                .map(|p| p.name.clone())
+                .collect();
+            print_choices(&choices);
+            Question {
+                prompt: format!("Which province is {} in?", city.name(Language::Fr)),
+                expected: quiz
+                    .provinces()
+                    .province_of(city.loc)
+                    .map_or("?".to_string(), |p| p.name.clone()),
+                choices,
+                language: Language::Fr,
+            }
+        }
+        Mode::Translate => {
+            let (from, to) = if rng.random_bool(0.5) {
+                (Language::Nl, Language::Fr)
+            } else {
+                (Language::Fr, Language::Nl)
+            };
+            Question {
+                prompt: format!("{} ({}) in {}?", city.name(from), from.code(), to.code()),
+                expected: city.name(to).to_string(),
+                choices: Vec::new(),
+                language: to,
+            }
+        }
+        Mode::Choice => {
+            println!("{}", minimap(quiz.provinces(), Some(city.loc)));
+            // Neighbours make for harder choices than any city
+            let guess_set = quiz.guess_set();
+            let mut choices: Vec<String> = guess_set
+                .index()
+                .k_nearest(city.loc, NEIGHBOURS + 1)
+                .into_iter()
+                .map(|i| &guess_set.all_cities()[i])
+                .filter(|c| c.names.default != city.names.default)
+                .choose_multiple(&mut rng, CHOICES - 1)
+                .into_iter()
+                .map(|c| c.name(Language::Fr).to_string())
+                .collect();
+            choices.push(city.name(Language::Fr).to_string());
+            choices.shuffle(&mut rng);
+            print_choices(&choices);
+            Question {
+                prompt: "Which city is marked with @?".to_string(),
+                expected: city.name(Language::Fr).to_string(),
+                choices,
+                language: Language::Fr,
+            }
+        }
+    }
+}
+
+fn print_choices(choices: &[String]) {
+    for (i, choice) in choices.iter().enumerate() {
+        println!("  {:>2}) {choice}", i + 1);
+    }
+}
+
+/// Numbers pick one of the choices, anything else is taken as typed.
+fn to_answer(mode: Mode, quiz: &Quiz, question: &Question, line: &str) -> Answer {
+    let picked = line
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| question.choices.get(i));
+    let text = picked.map_or(line, String::as_str).to_string();
+    match mode {
+        Mode::Province => {
+            let id = picked
+                .and_then(|name| quiz.provinces().provinces.iter().find(|p| &p.name == name))
+                .map_or(text, |p| p.id.clone());
+            Answer::Province(id)
+        }
+        Mode::Translate => Answer::Translation(question.language, text),
+        Mode::Choice => Answer::Name(text),
+    }
+}
+
+/// ASCII map of the provinces, `:` for Flanders, `.` for Wallonia and `#`
+/// for Brussels, with `@` at `marker`.
+fn minimap(provinces: &ProvinceMap, marker: Option<Point>) -> String {
+    let cell = Point::new(
+        provinces.size.x / MINIMAP_WIDTH as f32,
+        provinces.size.y / MINIMAP_HEIGHT as f32,
+    );
+    let to_cell = |p: Point| {
+        let col = (p.x + provinces.size.x / 2.) / cell.x;
+        let row = (provinces.size.y / 2. - p.y) / cell.y;
+        (row as usize, col as usize)
+    };
+    let marker = marker.map(to_cell);
+
+    let mut map = String::new();
+    for row in 0..MINIMAP_HEIGHT {
+        for col in 0..MINIMAP_WIDTH {
+            let center = Point::new(
+                (col as f32 + 0.5) * cell.x - provinces.size.x / 2.,
+                provinces.size.y / 2. - (row as f32 + 0.5) * cell.y,
+            );
+            let c = if marker == Some((row, col)) {
+                '@'
+            } else {
+                match provinces.region_of(center) {
+                    Some(Region::Flanders) => ':',
+                    Some(Region::Wallonia | Region::GermanSpeaking) => '.',
+                    Some(Region::Brussels) => '#',
+                    None => ' ',
+                }
+            };
+            map.push(c);
+        }
+        map.push('\n');
+    }
+    map
+}
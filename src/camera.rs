@@ -1,29 +1,87 @@
+use bevy::asset::UnapprovedPathMode;
 use bevy::camera::Camera2d;
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
 use bevy_svg::prelude::*;
-// use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
-// use bevy::sprite::{Wireframe2dConfig, Wireframe2dPlugin};
+
+use crate::types::{
+    City, GameConfig, GuessType, Location, MapSize, SpawnCity, ValidatedGuess, WorldClickCatcher,
+};
+
+/// Closest zoom, 10 times the default scale.
+const MIN_SCALE: f32 = 0.1;
+/// Default zoom, showing the whole map.
+const MAX_SCALE: f32 = 1.0;
+/// Relative scale change per wheel line.
+const ZOOM_STEP: f32 = 0.1;
+/// Pixels scrolled by touchpads for one wheel line.
+const PIXELS_PER_LINE: f32 = 100.;
+/// Max delay between the two clicks of a double click, in seconds.
+const DOUBLE_CLICK_DELAY: f32 = 0.3;
+/// Margin kept around the guess and the answer when framing them.
+const FRAME_PADDING: f32 = 60.;
+/// Rate of the framing animation, higher is faster.
+const FLIGHT_SPEED: f32 = 4.;
+
+/// Where the camera is heading, if it is animated.
+#[derive(Resource, Default)]
+pub struct CameraFlight(pub Option<CameraTarget>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct CameraTarget {
+    pub center: Vec2,
+    pub scale: f32,
+}
+
+impl CameraTarget {
+    pub const FULL_MAP: CameraTarget = CameraTarget {
+        center: Vec2::ZERO,
+        scale: MAX_SCALE,
+    };
+}
 
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ClearColor(Color::hsv(1.0, 0.0, 0.80)));
-        app.add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "GeoQuizz Belgium".to_string(),
-                ..Default::default()
-            }),
-            ..Default::default()
-        }));
+        app.add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "GeoQuizz Belgium".to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                // `--map` may point outside of the assets folder
+                .set(AssetPlugin {
+                    unapproved_path_mode: UnapprovedPathMode::Allow,
+                    ..Default::default()
+                }),
+        );
         app.add_plugins(SvgPlugin);
+        app.init_resource::<CameraFlight>();
+        app.init_resource::<GameConfig>();
         app.add_systems(Startup, setup);
-        // app.add_systems(Update, camera_zoom_system);
+        app.add_systems(
+            Update,
+            (
+                frame_reveal.run_if(on_message::<ValidatedGuess>),
+                frame_full_map.run_if(on_message::<SpawnCity>),
+                animate_camera,
+                camera_zoom_system,
+                camera_pan_system,
+                camera_reset_system,
+                clamp_camera_system,
+            )
+                .chain(),
+        );
     }
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let svg = asset_server.load("belgium_map.svg");
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, config: Res<GameConfig>) {
+    let svg = asset_server.load(config.map.clone());
     commands.spawn((Camera2d, Msaa::Sample4));
     commands.spawn((
         Svg2d(svg),
@@ -31,28 +89,173 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 }
 
-// fn camera_zoom_system(
-//     mut evr_scroll: EventReader<MouseWheel>,
-//     mut camera: Query<(Option<Mut<Projection>>, Mut<Transform>), With<Camera>>,
-// ) {
-//     for ev in evr_scroll.read() {
-//         for (projection, mut transform) in camera.iter_mut() {
-//             let amount = match ev.unit {
-//                 MouseScrollUnit::Line => ev.y,
-//                 MouseScrollUnit::Pixel => ev.y,
-//             };
-//             if let Some(mut projection) = projection {
-//                 if let Projection::Orthographic(ref mut projection) = *projection {
-//                     projection.scale -= if projection.scale <= 1.0 {
-//                         amount * 0.05
-//                     } else {
-//                         amount
-//                     };
-//                     projection.scale = projection.scale.clamp(0.01, 10.0);
-//                 }
-//             } else {
-//                 transform.translation.z -= amount;
-//             }
-//         }
-//     }
-// }
+/// Fit both the guess and the answer in view once the answer is revealed.
+fn frame_reveal(
+    mut flight: ResMut<CameraFlight>,
+    city: Query<&Location, With<City>>,
+    guess: Query<&GuessType>,
+    camera: Query<&Camera, With<Camera2d>>,
+    map: Res<MapSize>,
+) {
+    let Ok(Location(answer)) = city.single() else {
+        return;
+    };
+    let guess = match guess.single() {
+        Ok(GuessType::Location(pos)) => *pos,
+        _ => *answer,
+    };
+    let Some(viewport) = camera.single().ok().and_then(|c| c.logical_viewport_size()) else {
+        return;
+    };
+    let size = (*answer - guess).abs() + Vec2::splat(2. * FRAME_PADDING);
+    let scale = (size / viewport).max_element().clamp(MIN_SCALE, MAX_SCALE);
+    flight.0 = Some(CameraTarget {
+        center: clamp_center((*answer + guess) / 2., scale, viewport, map.0),
+        scale,
+    });
+}
+
+/// Go back to the whole map for the next city.
+fn frame_full_map(mut flight: ResMut<CameraFlight>) {
+    flight.0 = Some(CameraTarget::FULL_MAP);
+}
+
+fn animate_camera(
+    time: Res<Time>,
+    mut flight: ResMut<CameraFlight>,
+    mut camera: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    let Some(target) = flight.0 else {
+        return;
+    };
+    let Ok((mut transform, mut projection)) = camera.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ref mut projection) = *projection else {
+        return;
+    };
+    let t = 1. - (-FLIGHT_SPEED * time.delta_secs()).exp();
+    let center = transform.translation.truncate().lerp(target.center, t);
+    projection.scale += (target.scale - projection.scale) * t;
+    transform.translation = center.extend(transform.translation.z);
+
+    let arrived =
+        center.distance(target.center) < 0.5 && (projection.scale - target.scale).abs() < 0.001;
+    if arrived {
+        projection.scale = target.scale;
+        transform.translation = target.center.extend(transform.translation.z);
+        flight.0 = None;
+    }
+}
+
+/// Zoom toward the cursor with the mouse wheel.
+fn camera_zoom_system(
+    mut evr_scroll: MessageReader<MouseWheel>,
+    windows: Query<&Window>,
+    mut camera: Query<(&Camera, &GlobalTransform, &mut Transform, &mut Projection)>,
+    mut flight: ResMut<CameraFlight>,
+) {
+    let Ok((camera, camera_transform, mut transform, mut projection)) = camera.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ref mut projection) = *projection else {
+        return;
+    };
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|pos| camera.viewport_to_world_2d(camera_transform, pos).ok());
+
+    for ev in evr_scroll.read() {
+        // The player takes over the camera
+        flight.0 = None;
+        let amount = match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / PIXELS_PER_LINE,
+        };
+        let old_scale = projection.scale;
+        let new_scale = (old_scale * (1. - amount * ZOOM_STEP)).clamp(MIN_SCALE, MAX_SCALE);
+        projection.scale = new_scale;
+
+        // Keep the point under the cursor in place
+        if let Some(cursor) = cursor {
+            let center = transform.translation.truncate();
+            let shifted = cursor + (center - cursor) * new_scale / old_scale;
+            transform.translation = shifted.extend(transform.translation.z);
+        }
+    }
+}
+
+/// Pan the map while the right button is held.
+fn camera_pan_system(
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut evr_motion: MessageReader<MouseMotion>,
+    mut camera: Query<(&mut Transform, &Projection), With<Camera2d>>,
+    mut flight: ResMut<CameraFlight>,
+) {
+    let delta: Vec2 = evr_motion.read().map(|ev| ev.delta).sum();
+    if !buttons.pressed(MouseButton::Right) || delta == Vec2::ZERO {
+        return;
+    }
+    flight.0 = None;
+    let Ok((mut transform, Projection::Orthographic(projection))) = camera.single_mut() else {
+        return;
+    };
+    // Screen y points down, world y points up
+    transform.translation.x -= delta.x * projection.scale;
+    transform.translation.y += delta.y * projection.scale;
+}
+
+/// Double click on the map restores the default view.
+fn camera_reset_system(
+    clicks: Query<&Interaction, (Changed<Interaction>, With<WorldClickCatcher>)>,
+    time: Res<Time>,
+    mut last_click: Local<Option<f32>>,
+    mut camera: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+    mut flight: ResMut<CameraFlight>,
+) {
+    if !clicks.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    let now = time.elapsed_secs();
+    let is_double = last_click.is_some_and(|last| now - last < DOUBLE_CLICK_DELAY);
+    *last_click = Some(now);
+    if !is_double {
+        return;
+    }
+    if let Ok((mut transform, mut projection)) = camera.single_mut()
+        && let Projection::Orthographic(ref mut projection) = *projection
+    {
+        projection.scale = MAX_SCALE;
+        transform.translation = Vec3::ZERO.with_z(transform.translation.z);
+        *last_click = None;
+        flight.0 = None;
+    }
+}
+
+/// Keep the map covering the view, or centered when it is smaller than the view.
+fn clamp_camera_system(
+    mut camera: Query<(&Camera, &mut Transform, &Projection)>,
+    map: Res<MapSize>,
+) {
+    let Ok((camera, mut transform, Projection::Orthographic(projection))) = camera.single_mut()
+    else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    let clamped = clamp_center(
+        transform.translation.truncate(),
+        projection.scale,
+        viewport,
+        map.0,
+    );
+    transform.translation = clamped.extend(transform.translation.z);
+}
+
+fn clamp_center(center: Vec2, scale: f32, viewport: Vec2, map_size: Vec2) -> Vec2 {
+    let max_offset = (map_size / 2. - viewport * scale / 2.).max(Vec2::ZERO);
+    center.clamp(-max_offset, max_offset)
+}
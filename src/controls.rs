@@ -0,0 +1,111 @@
+use bevy::{
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
+};
+
+use crate::{
+    game::place_guess,
+    types::{
+        ConfirmPressed, Exploring, GameConfig, GameState, GuessAssets, GuessType, HintPressed,
+        PauseOverlay, Paused,
+    },
+};
+
+/// Guess marker displacement per key press, in map units.
+const SMALL_STEP: f32 = 2.;
+/// Displacement when `Shift` is held.
+const LARGE_STEP: f32 = 20.;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Paused>().add_systems(
+            Update,
+            (
+                keyboard_controls,
+                update_pause_overlay.run_if(resource_changed::<Paused>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// `Enter`/`Space` confirm or continue, `H` asks for a hint, arrows/WASD move
+/// the guess marker, `Backspace` clears it and `Esc` toggles the pause. Only
+/// `Esc` works while exploring.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn keyboard_controls(
+    mut commands: Commands,
+    mut keyboard: MessageReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    guess_assets: Res<GuessAssets>,
+    mut guess: Query<(Entity, &mut Transform), With<GuessType>>,
+    game_state: Res<GameState>,
+    mut paused: ResMut<Paused>,
+    exploring: Res<Exploring>,
+    config: Res<GameConfig>,
+    mut confirm_event: MessageWriter<ConfirmPressed>,
+    mut hint_event: MessageWriter<HintPressed>,
+) {
+    let step = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        LARGE_STEP
+    } else {
+        SMALL_STEP
+    };
+    let mut nudge = Vec2::ZERO;
+    for ev in keyboard.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+        if ev.key_code == KeyCode::Escape {
+            paused.0 = !paused.0;
+            continue;
+        }
+        // The explorer takes the keyboard for its search
+        if paused.0 || exploring.0 {
+            continue;
+        }
+        match ev.key_code {
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space if !ev.repeat => {
+                confirm_event.write(ConfirmPressed);
+            }
+            KeyCode::KeyH if !ev.repeat => {
+                hint_event.write(HintPressed);
+            }
+            KeyCode::Backspace if *game_state == GameState::Guess => {
+                if let Ok((entity, _)) = guess.single() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            KeyCode::ArrowUp | KeyCode::KeyW => nudge.y += step,
+            KeyCode::ArrowDown | KeyCode::KeyS => nudge.y -= step,
+            KeyCode::ArrowLeft | KeyCode::KeyA => nudge.x -= step,
+            KeyCode::ArrowRight | KeyCode::KeyD => nudge.x += step,
+            _ => {}
+        }
+    }
+
+    // Region quizzes are answered with their buttons only
+    if nudge != Vec2::ZERO && *game_state == GameState::Guess && !config.mode.is_region_quiz() {
+        let existing = guess.single_mut().ok();
+        // Without a marker yet, start from the center of the map
+        let from = existing.as_ref().map_or(Vec2::ZERO, |(_, transform)| {
+            transform.translation.truncate()
+        });
+        place_guess(&mut commands, &guess_assets, existing, from + nudge);
+    }
+}
+
+fn update_pause_overlay(
+    paused: Res<Paused>,
+    mut overlay: Query<&mut Visibility, With<PauseOverlay>>,
+) {
+    for mut vis in &mut overlay {
+        *vis = if paused.0 {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
@@ -0,0 +1,48 @@
+//! Quiz logic without any Bevy dependency: loading the dataset, projecting
+//! cities on the map, drawing the questions and scoring the answers.
+//!
+//! Available with the `core` feature alone, the Bevy game being one
+//! frontend over it.
+
+mod city;
+mod dataset;
+mod error;
+mod files;
+mod hints;
+mod history;
+mod i18n;
+mod labels;
+#[cfg(feature = "data")]
+pub mod osm;
+mod projection;
+mod quiz;
+mod regions;
+mod scoring;
+mod selection;
+mod spatial;
+mod study;
+mod validation;
+
+pub use city::{CityId, CityNames, CityRecord, Place, fold_name};
+pub use dataset::{
+    DatasetFormat, DatasetOptions, NameKeys, load_database, load_database_with, parse_csv,
+    parse_database, parse_geojson,
+};
+pub use error::{GeoError, QuizError};
+pub use files::json_file;
+pub use hints::{HINT_CIRCLE_KM, Hint, MAX_HINTS, max_points_with_hints};
+pub use history::{CityStats, History};
+pub use i18n::{Language, UiStrings};
+pub use labels::{LabelBox, LabelRequest, place_labels};
+pub use projection::{
+    CONTROL_POINTS, ControlPoint, Point, distance_km, local_to_oms, oms_to_local,
+};
+pub use quiz::{
+    Answer, Difficulty, Distance, GuessResult, GuessSet, Quiz, ROUND_LENGTH, Surroundings,
+};
+pub use regions::{OUTLINE_TOLERANCE_KM, Outline, Province, ProvinceMap, Region};
+pub use scoring::{Score, ScoringCurve};
+pub use selection::{NoRepeats, SelectionStrategy, Spread, Weighted};
+pub use spatial::{CityIndex, DEFAULT_CELL_SIZE};
+pub use study::{StudyMatch, StudySet, match_study_list, parse_study_list};
+pub use validation::{NEAR_DUPLICATE_KM, ValidationReport, validate_database};
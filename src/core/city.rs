@@ -0,0 +1,161 @@
+use super::{i18n::Language, projection::Point};
+
+/// Identifies a city across languages, renames and database rebuilds: the
+/// id of its OpenStreetMap node.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Component))]
+pub struct CityId(pub u64);
+
+impl CityId {
+    /// Set on ids not coming from OpenStreetMap, far above any node id.
+    const SYNTHETIC: u64 = 1 << 63;
+
+    /// Id of a city without OSM id, derived from its name and coordinates.
+    /// Stable as long as they do not change, so rebuilding the database
+    /// with ids is still needed.
+    pub fn synthetic(name: &str, lat: f32, lon: f32) -> Self {
+        // FNV-1a, which unlike `DefaultHasher` is fixed across Rust versions
+        let bytes = name
+            .bytes()
+            .chain(lat.to_le_bytes())
+            .chain(lon.to_le_bytes());
+        let hash = bytes.fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        Self(hash | Self::SYNTHETIC)
+    }
+
+    pub fn is_synthetic(self) -> bool {
+        self.0 & Self::SYNTHETIC != 0
+    }
+}
+
+/// Every known name of a city, `default` being the one used on site.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Component))]
+pub struct CityNames {
+    pub default: String,
+    pub fr: Option<String>,
+    pub nl: Option<String>,
+    pub de: Option<String>,
+    pub en: Option<String>,
+}
+
+impl CityNames {
+    pub fn get(&self, language: Language) -> &str {
+        let name = match language {
+            Language::Fr => &self.fr,
+            Language::Nl => &self.nl,
+            Language::De => &self.de,
+            Language::En => &self.en,
+            Language::Local => &None,
+        };
+        name.as_deref().unwrap_or(&self.default)
+    }
+
+    /// Every name, starting with the default one.
+    pub fn all(&self) -> impl Iterator<Item = &str> {
+        let translated = [&self.fr, &self.nl, &self.de, &self.en]
+            .into_iter()
+            .filter_map(|name| name.as_deref());
+        std::iter::once(self.default.as_str()).chain(translated)
+    }
+}
+
+/// Kind of settlement, from the OSM `place` tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Place {
+    City,
+    Town,
+    Village,
+}
+
+impl Place {
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "city" => Some(Place::City),
+            "town" => Some(Place::Town),
+            "village" => Some(Place::Village),
+            _ => None,
+        }
+    }
+}
+
+/// A city of the dataset, with its geographic and map coordinates.
+#[derive(Debug, Clone)]
+pub struct CityRecord {
+    pub id: CityId,
+    pub names: CityNames,
+    pub lat: f32,
+    pub lon: f32,
+    pub loc: Point,
+    pub place: Option<Place>,
+    pub population: Option<u64>,
+    /// Admin level the city is the capital of: 2 for the country, 4 for a
+    /// region, 6 for a province.
+    pub capital: Option<u8>,
+}
+
+impl CityRecord {
+    pub fn name(&self, language: Language) -> &str {
+        self.names.get(language)
+    }
+
+    /// Capital of a province, a region or the country.
+    pub fn is_major_capital(&self) -> bool {
+        self.capital.is_some_and(|level| level <= 6)
+    }
+
+    /// Weight of the city when drawing questions, well known cities being
+    /// asked more often. Grows with the square root of the population, so a
+    /// city 100 times larger comes up 10 times more.
+    pub fn importance(&self) -> f64 {
+        let size = match (self.population, self.place) {
+            (Some(population), _) => population as f64,
+            (None, Some(Place::City)) => 100_000.,
+            (None, Some(Place::Village)) => 1_000.,
+            (None, _) => 10_000.,
+        };
+        let capital = match self.capital {
+            Some(2) => 3.,
+            Some(..=4) => 2.,
+            Some(..=6) => 1.5,
+            _ => 1.,
+        };
+        size.max(1.).sqrt() * capital
+    }
+
+    /// Whether `answer` is one of the city names, ignoring case and
+    /// surrounding spaces.
+    pub fn matches_name(&self, answer: &str) -> bool {
+        let answer = answer.trim().to_lowercase();
+        self.names
+            .all()
+            .any(|name| name.trim().to_lowercase() == answer)
+    }
+}
+
+/// Lowercase, without accents, and with hyphens and apostrophes turned into
+/// spaces: `Saint-Hubert` and `saint hubert` fold the same.
+pub fn fold_name(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in name.to_lowercase().chars() {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => folded.push('a'),
+            'ç' => folded.push('c'),
+            'è' | 'é' | 'ê' | 'ë' => folded.push('e'),
+            'ì' | 'í' | 'î' | 'ï' => folded.push('i'),
+            'ñ' => folded.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => folded.push('o'),
+            'ù' | 'ú' | 'û' | 'ü' => folded.push('u'),
+            'ý' | 'ÿ' => folded.push('y'),
+            'æ' => folded.push_str("ae"),
+            'œ' => folded.push_str("oe"),
+            '-' | '\'' | '’' | '.' | ',' => folded.push(' '),
+            c => folded.push(c),
+        }
+    }
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
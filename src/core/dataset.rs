@@ -0,0 +1,420 @@
+use std::path::Path;
+
+use super::{
+    city::{CityId, CityNames, CityRecord, Place},
+    error::GeoError,
+    projection::{Point, oms_to_local},
+};
+
+#[derive(Debug, serde::Deserialize)]
+struct OsmCity {
+    /// OSM node id, missing from databases built before it was kept.
+    id: Option<u64>,
+    /// Missing names are reported by the validation rather than failing the
+    /// whole parse.
+    #[serde(rename = "name:default")]
+    name_default: Option<String>,
+    #[serde(rename = "name:fr")]
+    name_fr: Option<String>,
+    #[serde(rename = "name:nl")]
+    name_nl: Option<String>,
+    #[serde(rename = "name:de")]
+    name_de: Option<String>,
+    #[serde(rename = "name:en")]
+    name_en: Option<String>,
+    lat: f32,
+    lon: f32,
+    place: Option<String>,
+    population: Option<u64>,
+    /// `yes` or the admin level of the capital.
+    capital: Option<String>,
+    admin_level: Option<String>,
+}
+
+impl OsmCity {
+    /// A city described by key-value properties, empty values being missing.
+    fn from_properties(
+        lat: f32,
+        lon: f32,
+        property: impl Fn(&str) -> Option<String>,
+        names: &NameKeys,
+    ) -> Self {
+        let property = |key: &str| property(key).filter(|value| !value.is_empty());
+        Self {
+            // Overpass turbo writes `node/123`
+            id: property("id")
+                .or_else(|| property("@id"))
+                .and_then(|id| id.trim_start_matches("node/").parse().ok()),
+            name_default: property(&names.default),
+            name_fr: property(&names.fr),
+            name_nl: property(&names.nl),
+            name_de: property(&names.de),
+            name_en: property(&names.en),
+            lat,
+            lon,
+            place: property("place"),
+            population: property("population").and_then(|p| p.parse().ok()),
+            capital: property("capital"),
+            admin_level: property("admin_level"),
+        }
+    }
+
+    /// OSM tags places as `capital=yes` with the level in `admin_level`,
+    /// or directly as `capital=4`.
+    fn capital_level(&self) -> Option<u8> {
+        match self.capital.as_deref()? {
+            "yes" => Some(
+                self.admin_level
+                    .as_deref()
+                    .and_then(|level| level.parse().ok())
+                    .unwrap_or(2),
+            ),
+            level => level.parse().ok(),
+        }
+    }
+}
+
+/// Format of a city dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetFormat {
+    /// JSON array written by `geoquizz-data build`.
+    Osm,
+    /// `FeatureCollection` of `Point` features.
+    GeoJson,
+    /// One city per row, below a header naming the columns.
+    Csv,
+}
+
+impl DatasetFormat {
+    /// From the extension, or the first character for `.json` files and
+    /// unknown extensions.
+    pub fn detect(path: &Path, content: &str) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("geojson") => DatasetFormat::GeoJson,
+            Some("csv" | "tsv") => DatasetFormat::Csv,
+            _ => match content.trim_start().chars().next() {
+                Some('[') => DatasetFormat::Osm,
+                Some('{') => DatasetFormat::GeoJson,
+                _ => DatasetFormat::Csv,
+            },
+        }
+    }
+}
+
+/// Keys of the names in GeoJSON properties or CSV columns.
+#[derive(Debug, Clone)]
+pub struct NameKeys {
+    pub default: String,
+    pub fr: String,
+    pub nl: String,
+    pub de: String,
+    pub en: String,
+}
+
+impl Default for NameKeys {
+    /// OSM tags, as in GeoJSON exports of Overpass turbo.
+    fn default() -> Self {
+        Self {
+            default: "name".into(),
+            fr: "name:fr".into(),
+            nl: "name:nl".into(),
+            de: "name:de".into(),
+            en: "name:en".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DatasetOptions {
+    /// Detected if `None`.
+    pub format: Option<DatasetFormat>,
+    pub names: NameKeys,
+}
+
+pub fn load_database<P: AsRef<Path>>(path: P) -> Result<Vec<CityRecord>, GeoError> {
+    load_database_with(path, &DatasetOptions::default())
+}
+
+pub fn load_database_with<P: AsRef<Path>>(
+    path: P,
+    options: &DatasetOptions,
+) -> Result<Vec<CityRecord>, GeoError> {
+    let path = path.as_ref();
+    let file = std::fs::read_to_string(path).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    match options
+        .format
+        .unwrap_or_else(|| DatasetFormat::detect(path, &file))
+    {
+        DatasetFormat::Osm => parse_database(&file),
+        DatasetFormat::GeoJson => parse_geojson(&file, &options.names),
+        DatasetFormat::Csv => parse_csv(&file, &options.names),
+    }
+}
+
+pub fn parse_database(content: &str) -> Result<Vec<CityRecord>, GeoError> {
+    let oms_cities: Vec<OsmCity> =
+        serde_json::from_str(content).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    let cities: Vec<CityRecord> = oms_cities.into_iter().map(|city| city.into()).collect();
+    Ok(cities)
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct FeatureCollection {
+    features: Vec<Feature>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Feature {
+    geometry: Option<Geometry>,
+    #[serde(default)]
+    properties: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Geometry {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    coordinates: Vec<serde_json::Value>,
+}
+
+pub fn parse_geojson(content: &str, names: &NameKeys) -> Result<Vec<CityRecord>, GeoError> {
+    let collection: FeatureCollection =
+        serde_json::from_str(content).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    let mut cities = Vec::new();
+    for (index, feature) in collection.features.into_iter().enumerate() {
+        let error = |reason: &str| GeoError::DataLoading(format!("feature {index}: {reason}"));
+        let geometry = feature.geometry.ok_or_else(|| error("no geometry"))?;
+        if geometry.kind != "Point" {
+            return Err(error(&format!("{} instead of a Point", geometry.kind)));
+        }
+        let coordinate = |i: usize| {
+            geometry
+                .coordinates
+                .get(i)
+                .and_then(serde_json::Value::as_f64)
+                .map(|c| c as f32)
+                .ok_or_else(|| error("bad coordinates"))
+        };
+        // GeoJSON puts the longitude first
+        let (lon, lat) = (coordinate(0)?, coordinate(1)?);
+        let property = |key: &str| match feature.properties.get(key)? {
+            serde_json::Value::String(value) => Some(value.trim().to_string()),
+            serde_json::Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        };
+        cities.push(OsmCity::from_properties(lat, lon, property, names).into());
+    }
+    Ok(cities)
+}
+
+/// Columns may be separated by `,`, `;` or tabs, and coordinates may use
+/// a decimal comma, as spreadsheets set to French or Dutch export them.
+pub fn parse_csv(content: &str, names: &NameKeys) -> Result<Vec<CityRecord>, GeoError> {
+    let header = content.lines().next().unwrap_or_default();
+    let delimiter = [b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|d| header.bytes().filter(|b| b == d).count())
+        .unwrap_or(b',');
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| GeoError::DataLoading(e.to_string()))?
+        .iter()
+        .map(str::to_lowercase)
+        .collect();
+    let column = |keys: &[&str]| {
+        headers
+            .iter()
+            .position(|h| keys.iter().any(|k| k.eq_ignore_ascii_case(h)))
+    };
+    let missing = |what: &str| GeoError::DataLoading(format!("no {what} column"));
+    let name_column = column(&[&names.default]).ok_or_else(|| missing(&names.default))?;
+    let lat_column = column(&["lat", "latitude"]).ok_or_else(|| missing("lat"))?;
+    let lon_column = column(&["lon", "lng", "long", "longitude"]).ok_or_else(|| missing("lon"))?;
+
+    let mut cities = Vec::new();
+    for (index, row) in reader.records().enumerate() {
+        let row = row.map_err(|e| GeoError::DataLoading(e.to_string()))?;
+        let line = index + 2;
+        let coordinate = |i: usize| {
+            row.get(i)
+                .and_then(|c| c.replace(',', ".").parse::<f32>().ok())
+                .ok_or_else(|| GeoError::DataLoading(format!("line {line}: bad coordinates")))
+        };
+        let (lat, lon) = (coordinate(lat_column)?, coordinate(lon_column)?);
+        let property = |key: &str| {
+            let value = row.get(if key == names.default {
+                name_column
+            } else {
+                column(&[key])?
+            })?;
+            Some(value.to_string())
+        };
+        cities.push(OsmCity::from_properties(lat, lon, property, names).into());
+    }
+    Ok(cities)
+}
+
+impl From<OsmCity> for CityRecord {
+    fn from(value: OsmCity) -> Self {
+        let (x, y) = oms_to_local(value.lon, value.lat);
+        let capital = value.capital_level();
+        let name_default = value.name_default.unwrap_or_default();
+        let id = value.id.map_or_else(
+            || CityId::synthetic(&name_default, value.lat, value.lon),
+            CityId,
+        );
+        let names = CityNames {
+            default: name_default,
+            fr: value.name_fr,
+            nl: value.name_nl,
+            de: value.name_de,
+            en: value.name_en,
+        };
+        CityRecord {
+            id,
+            names,
+            lat: value.lat,
+            lon: value.lon,
+            loc: Point::new(x, y),
+            place: value.place.as_deref().and_then(Place::from_tag),
+            population: value.population,
+            capital,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Language;
+
+    #[test]
+    // Coordinates as exported by OSM
+    #[allow(clippy::excessive_precision)]
+    fn test_city_names_fallback() {
+        let city: CityRecord = OsmCity {
+            id: None,
+            name_default: Some("Saint-Gilles - Sint-Gillis".to_string()),
+            name_fr: Some("Saint-Gilles".to_string()),
+            name_nl: Some("Sint-Gillis".to_string()),
+            name_de: None,
+            name_en: None,
+            lat: 50.8249958,
+            lon: 4.3454841,
+            place: None,
+            population: None,
+            capital: None,
+            admin_level: None,
+        }
+        .into();
+        assert_eq!(city.name(Language::default()), "Saint-Gilles");
+        assert_eq!(city.name(Language::Nl), "Sint-Gillis");
+        assert_eq!(city.name(Language::De), "Saint-Gilles - Sint-Gillis");
+        assert_eq!(city.name(Language::Local), "Saint-Gilles - Sint-Gillis");
+    }
+
+    #[test]
+    fn test_load_database() {
+        let cities = load_database("database/belgium_cities.json").unwrap();
+        assert!(!cities.is_empty());
+    }
+
+    #[test]
+    fn test_place_metadata() {
+        let json = r#"[
+            {"name:default": "Mons", "lat": 50.45, "lon": 3.95, "place": "city",
+             "population": 95299, "capital": "6"},
+            {"name:default": "Namur", "lat": 50.46, "lon": 4.87, "place": "city",
+             "capital": "yes", "admin_level": "4"},
+            {"name:default": "Havré", "lat": 50.46, "lon": 4.04, "place": "village"}
+        ]"#;
+        let cities = parse_database(json).unwrap();
+        assert_eq!(cities[0].population, Some(95_299));
+        assert_eq!(cities[0].capital, Some(6));
+        assert_eq!(cities[1].capital, Some(4));
+        assert_eq!(cities[2].place, Some(Place::Village));
+        assert!(cities[1].importance() > cities[0].importance());
+        assert!(cities[0].importance() > cities[2].importance());
+    }
+
+    #[test]
+    fn test_parse_geojson() {
+        let json = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "id": "node/42",
+                 "geometry": {"type": "Point", "coordinates": [3.95, 50.45]},
+                 "properties": {"@id": "node/42", "name": "Mons", "name:nl": "Bergen",
+                                "population": 95299}},
+                {"type": "Feature",
+                 "geometry": {"type": "Point", "coordinates": [3.78, 50.63]},
+                 "properties": {"ville": "Ath"}}
+            ]
+        }"#;
+        let cities = parse_geojson(json, &NameKeys::default()).unwrap();
+        assert_eq!(cities[0].id, CityId(42));
+        assert_eq!(cities[0].name(Language::Nl), "Bergen");
+        assert_eq!(cities[0].population, Some(95_299));
+        assert_eq!((cities[0].lat, cities[0].lon), (50.45, 3.95));
+
+        let names = NameKeys {
+            default: "ville".into(),
+            ..Default::default()
+        };
+        let cities = parse_geojson(json, &names).unwrap();
+        assert_eq!(cities[1].names.default, "Ath");
+
+        let line = json.replace("Point", "LineString");
+        assert!(parse_geojson(&line, &names).is_err());
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let names = NameKeys::default();
+        let csv = "Name,Lat,Lon,name:nl\nMons,50.45,3.95,Bergen\n\"Ath, la ville\",50.63,3.78,\n";
+        let cities = parse_csv(csv, &names).unwrap();
+        assert_eq!(cities.len(), 2);
+        assert_eq!(cities[0].name(Language::Nl), "Bergen");
+        assert_eq!(cities[1].names.default, "Ath, la ville");
+        assert_eq!(cities[1].names.nl, None);
+
+        // Exported by a spreadsheet in French
+        let csv = "name;latitude;longitude\nMons;50,45;3,95\n";
+        let cities = parse_csv(csv, &names).unwrap();
+        assert_eq!((cities[0].lat, cities[0].lon), (50.45, 3.95));
+
+        assert!(parse_csv("ville;lat;lon\nMons;50,45;3,95\n", &names).is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let detect = |path: &str, content| DatasetFormat::detect(Path::new(path), content);
+        assert_eq!(detect("cities.json", "[]"), DatasetFormat::Osm);
+        assert_eq!(detect("cities.json", "{}"), DatasetFormat::GeoJson);
+        assert_eq!(detect("cities.geojson", "{}"), DatasetFormat::GeoJson);
+        assert_eq!(detect("cities.CSV", "name,lat,lon"), DatasetFormat::Csv);
+        assert_eq!(detect("cities", "name,lat,lon"), DatasetFormat::Csv);
+    }
+
+    #[test]
+    fn test_city_ids() {
+        let json = r#"[
+            {"name:default": "Ath", "id": 25, "lat": 50.63, "lon": 3.78},
+            {"name:default": "Ath", "lat": 50.63, "lon": 3.78}
+        ]"#;
+        let cities = parse_database(json).unwrap();
+        assert_eq!(cities[0].id, CityId(25));
+        assert!(cities[1].id.is_synthetic());
+        assert_eq!(cities[1].id, parse_database(json).unwrap()[1].id);
+    }
+}
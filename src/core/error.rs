@@ -0,0 +1,59 @@
+use thiserror::Error;
+
+use super::quiz::Difficulty;
+
+#[derive(Debug, Error)]
+pub enum GeoError {
+    #[error("Error while loading database {0}")]
+    DataLoading(String),
+    #[error("Error while writing database {0}")]
+    DataWriting(String),
+    #[error("Error while loading map {0}")]
+    MapLoading(String),
+    #[error("City #{index} ({name}): empty {field}")]
+    EmptyName {
+        index: usize,
+        name: String,
+        field: &'static str,
+    },
+    #[error("City #{index} ({name}): same name as city #{first}")]
+    DuplicateName {
+        index: usize,
+        name: String,
+        first: usize,
+    },
+    #[error("City #{index} ({name}): id {id} already used by city #{first}")]
+    DuplicateId {
+        index: usize,
+        name: String,
+        id: u64,
+        first: usize,
+    },
+    #[error("City #{index} ({name}): {km:.2} km from city #{other} ({other_name})")]
+    NearDuplicate {
+        index: usize,
+        name: String,
+        other: usize,
+        other_name: String,
+        km: f32,
+    },
+    #[error("City #{index} ({name}): {lat}, {lon} is outside of the map")]
+    OutOfBounds {
+        index: usize,
+        name: String,
+        lat: f32,
+        lon: f32,
+    },
+    #[error("City #{index} ({name}): outside of the country outline")]
+    OutsideCountry { index: usize, name: String },
+    #[error("{0:?} cannot name a file: it is empty or holds a path separator")]
+    InvalidFileName(String),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QuizError {
+    #[error("No question is waiting for an answer")]
+    NoQuestion,
+    #[error("No {} city to ask", .0.name())]
+    EmptyPool(Difficulty),
+}
@@ -0,0 +1,36 @@
+//! Files named after the player or the user, such as histories and study
+//! sets.
+
+use std::path::{Path, PathBuf};
+
+use super::error::GeoError;
+
+/// `<name>.json` in `dir`. Names that would leave `dir`, such as `../x` or
+/// `a/b`, are refused.
+pub fn json_file(dir: &Path, name: &str) -> Result<PathBuf, GeoError> {
+    let plain = !name.trim().is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':', '\0']);
+    if !plain {
+        return Err(GeoError::InvalidFileName(name.to_string()));
+    }
+    Ok(dir.join(format!("{name}.json")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_file() {
+        let dir = Path::new("saves");
+        assert_eq!(
+            json_file(dir, "jean.dupont").unwrap(),
+            Path::new("saves/jean.dupont.json")
+        );
+        for name in ["../x", "a/b", "a\\b", "..", "", " "] {
+            assert!(json_file(dir, name).is_err(), "{name:?}");
+        }
+    }
+}
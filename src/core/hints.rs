@@ -0,0 +1,41 @@
+//! Hints narrowing down where the city to find is, each one lowering the
+//! points the question can still give.
+
+use super::{projection::Point, regions::Region};
+
+/// Hints available per question, from the vaguest to the most precise.
+pub const MAX_HINTS: u32 = 3;
+
+/// Radius of the circle given as last hint.
+pub const HINT_CIRCLE_KM: f32 = 20.;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hint {
+    /// Name of the province of the city.
+    Province(String),
+    Region(Region),
+    /// Circle, in map units, the city is in. It is not centred on the city.
+    Circle {
+        center: Point,
+        radius: f32,
+    },
+}
+
+/// Most points a question can give once `hints` were used: each hint takes a
+/// quarter of `max` away.
+pub fn max_points_with_hints(max: u32, hints: u32) -> u32 {
+    max * (MAX_HINTS + 1).saturating_sub(hints) / (MAX_HINTS + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_points_with_hints() {
+        let points: Vec<_> = (0..=MAX_HINTS)
+            .map(|hints| max_points_with_hints(100, hints))
+            .collect();
+        assert_eq!(points, [100, 75, 50, 25]);
+    }
+}
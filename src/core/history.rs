@@ -0,0 +1,74 @@
+use std::{collections::BTreeMap, path::Path};
+
+use super::{city::CityId, error::GeoError, quiz::GuessResult};
+
+/// Answers given for one city.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CityStats {
+    pub asked: u32,
+    pub points: u32,
+    pub last_points: u32,
+}
+
+/// Results of a player, by city, saved between games.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+pub struct History {
+    pub cities: BTreeMap<CityId, CityStats>,
+}
+
+impl History {
+    /// An empty history if the file does not exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, GeoError> {
+        match std::fs::read_to_string(path) {
+            Ok(file) => {
+                serde_json::from_str(&file).map_err(|e| GeoError::DataLoading(e.to_string()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(GeoError::DataLoading(e.to_string())),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), GeoError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| GeoError::DataWriting(e.to_string()))?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| GeoError::DataWriting(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| GeoError::DataWriting(e.to_string()))
+    }
+
+    pub fn record(&mut self, result: &GuessResult) {
+        let stats = self.cities.entry(result.city.id).or_default();
+        stats.asked += 1;
+        stats.points += result.points;
+        stats.last_points = result.points;
+    }
+
+    pub fn stats(&self, id: CityId) -> Option<&CityStats> {
+        self.cities.get(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Answer, Quiz, load_database};
+
+    #[test]
+    fn test_record_and_reload() {
+        let mut quiz = Quiz::new(load_database("database/belgium_cities.json").unwrap());
+        let mut history = History::default();
+        let city = quiz.next_question().unwrap().clone();
+        history.record(&quiz.submit(&Answer::Location(city.loc)).unwrap());
+
+        let path = std::env::temp_dir().join("geoquizz_history_test.json");
+        history.save(&path).unwrap();
+        let reloaded = History::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let stats = reloaded.stats(city.id).unwrap();
+        assert_eq!((stats.asked, stats.points), (1, 100));
+    }
+}
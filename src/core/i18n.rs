@@ -0,0 +1,186 @@
+use super::regions::Region;
+
+/// Language used to display city names and interface texts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+pub enum Language {
+    #[default]
+    Fr,
+    Nl,
+    De,
+    En,
+    /// Name used on site: Dutch in Flanders, French in Wallonia,
+    /// both in Brussels.
+    Local,
+}
+
+/// Texts shown in the interface, one table per language.
+#[derive(Debug)]
+pub struct UiStrings {
+    pub find: &'static str,
+    pub score: &'static str,
+    pub confirm: &'static str,
+    pub next: &'static str,
+    pub new_round: &'static str,
+    pub paused: &'static str,
+    pub study_sets: &'static str,
+    pub all_cities: &'static str,
+    pub search: &'static str,
+    pub clicked_near: &'static str,
+    pub outside_country: &'static str,
+    pub hint: &'static str,
+    pub province: &'static str,
+    pub region: &'static str,
+    pub in_circle: &'static str,
+    pub province_bonus: &'static str,
+    pub flanders: &'static str,
+    pub wallonia: &'static str,
+    pub brussels: &'static str,
+    pub german_speaking: &'static str,
+}
+
+const FR: UiStrings = UiStrings {
+    find: "Trouvez ",
+    score: "Score : ",
+    confirm: "Valider",
+    next: "Continuer",
+    new_round: "Rejouer",
+    paused: "Pause — Échap pour reprendre",
+    study_sets: "Listes à étudier",
+    all_cities: "Toutes les villes",
+    search: "Rechercher : ",
+    clicked_near: "Cliqué près de ",
+    outside_country: "En dehors du pays, déplacez la réponse",
+    hint: "Indice",
+    province: "Province : ",
+    region: "Région : ",
+    in_circle: "La ville est dans le cercle",
+    province_bonus: "bonus province",
+    flanders: "Flandre",
+    wallonia: "Wallonie",
+    brussels: "Bruxelles",
+    german_speaking: "Communauté germanophone",
+};
+
+const NL: UiStrings = UiStrings {
+    find: "Zoek ",
+    score: "Score: ",
+    confirm: "Bevestig",
+    next: "Verder",
+    new_round: "Opnieuw",
+    paused: "Pauze — Esc om verder te gaan",
+    study_sets: "Leerlijsten",
+    all_cities: "Alle steden",
+    search: "Zoeken: ",
+    clicked_near: "Geklikt bij ",
+    outside_country: "Buiten het land, verplaats het antwoord",
+    hint: "Hint",
+    province: "Provincie: ",
+    region: "Gewest: ",
+    in_circle: "De stad ligt in de cirkel",
+    province_bonus: "provinciebonus",
+    flanders: "Vlaanderen",
+    wallonia: "Wallonië",
+    brussels: "Brussel",
+    german_speaking: "Duitstalige Gemeenschap",
+};
+
+const DE: UiStrings = UiStrings {
+    find: "Finde ",
+    score: "Punkte: ",
+    confirm: "Bestätigen",
+    next: "Weiter",
+    new_round: "Neue Runde",
+    paused: "Pause — Esc zum Fortsetzen",
+    study_sets: "Lernlisten",
+    all_cities: "Alle Städte",
+    search: "Suchen: ",
+    clicked_near: "Geklickt bei ",
+    outside_country: "Außerhalb des Landes, verschieben Sie die Antwort",
+    hint: "Hinweis",
+    province: "Provinz: ",
+    region: "Region: ",
+    in_circle: "Die Stadt liegt im Kreis",
+    province_bonus: "Provinzbonus",
+    flanders: "Flandern",
+    wallonia: "Wallonien",
+    brussels: "Brüssel",
+    german_speaking: "Deutschsprachige Gemeinschaft",
+};
+
+const EN: UiStrings = UiStrings {
+    find: "Find ",
+    score: "Score: ",
+    confirm: "Confirm",
+    next: "Continue",
+    new_round: "New round",
+    paused: "Paused — Esc to resume",
+    study_sets: "Study sets",
+    all_cities: "All cities",
+    search: "Search: ",
+    clicked_near: "Clicked near ",
+    outside_country: "Outside the country, move the guess",
+    hint: "Hint",
+    province: "Province: ",
+    region: "Region: ",
+    in_circle: "The city is in the circle",
+    province_bonus: "province bonus",
+    flanders: "Flanders",
+    wallonia: "Wallonia",
+    brussels: "Brussels",
+    german_speaking: "German-speaking Community",
+};
+
+impl UiStrings {
+    pub fn region(&self, region: Region) -> &'static str {
+        match region {
+            Region::Flanders => self.flanders,
+            Region::Wallonia => self.wallonia,
+            Region::Brussels => self.brussels,
+            Region::GermanSpeaking => self.german_speaking,
+        }
+    }
+}
+
+impl Language {
+    pub const ALL: [Language; 5] = [
+        Language::Fr,
+        Language::Nl,
+        Language::De,
+        Language::En,
+        Language::Local,
+    ];
+
+    pub fn strings(self) -> &'static UiStrings {
+        match self {
+            Language::Fr => &FR,
+            Language::Nl => &NL,
+            Language::De => &DE,
+            // Local names mix several languages, keep the interface neutral
+            Language::En | Language::Local => &EN,
+        }
+    }
+
+    /// Short code, as used on the command line.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Fr => "fr",
+            Language::Nl => "nl",
+            Language::De => "de",
+            Language::En => "en",
+            Language::Local => "local",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|l| l.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    /// Next language in [`Language::ALL`], wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
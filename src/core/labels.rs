@@ -0,0 +1,215 @@
+//! Placement of many city names around their dots, without overlaps.
+
+use std::collections::HashMap;
+
+use super::projection::Point;
+
+/// Axis aligned box, in map units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl LabelBox {
+    pub fn from_center(center: Point, size: Point) -> Self {
+        Self {
+            min: Point::new(center.x - size.x / 2., center.y - size.y / 2.),
+            max: Point::new(center.x + size.x / 2., center.y + size.y / 2.),
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        Point::new(
+            (self.min.x + self.max.x) / 2.,
+            (self.min.y + self.max.y) / 2.,
+        )
+    }
+
+    pub fn intersects(&self, other: &LabelBox) -> bool {
+        self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y
+    }
+
+    pub fn contains(&self, other: &LabelBox) -> bool {
+        self.min.x <= other.min.x
+            && self.min.y <= other.min.y
+            && other.max.x <= self.max.x
+            && other.max.y <= self.max.y
+    }
+}
+
+/// A label to put next to the dot at `anchor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelRequest {
+    pub anchor: Point,
+    pub size: Point,
+    /// Labels are placed by decreasing importance, the last ones being
+    /// dropped when there is no room left.
+    pub importance: f32,
+}
+
+/// Directions tried around a dot, preferred first: the classic cartographic
+/// order, right and above before left and below.
+const CANDIDATES: [(f32, f32); 8] = [
+    (1., 1.),
+    (1., -1.),
+    (-1., 1.),
+    (-1., -1.),
+    (1., 0.),
+    (-1., 0.),
+    (0., 1.),
+    (0., -1.),
+];
+
+/// Boxes bucketed by the grid cells they overlap, so that collisions are
+/// only checked against the boxes nearby.
+struct BoxGrid {
+    cell: Point,
+    cells: HashMap<(i32, i32), Vec<LabelBox>>,
+}
+
+impl BoxGrid {
+    /// Cells of `cell` size, best about the size of the largest box.
+    fn new(cell: Point) -> Self {
+        Self {
+            cell: Point::new(cell.x.max(f32::EPSILON), cell.y.max(f32::EPSILON)),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn keys(&self, b: &LabelBox) -> impl Iterator<Item = (i32, i32)> + use<> {
+        let (x0, y0) = self.key(b.min);
+        let (x1, y1) = self.key(b.max);
+        (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+    }
+
+    fn key(&self, point: Point) -> (i32, i32) {
+        (
+            (point.x / self.cell.x).floor() as i32,
+            (point.y / self.cell.y).floor() as i32,
+        )
+    }
+
+    fn insert(&mut self, b: LabelBox) {
+        for key in self.keys(&b) {
+            self.cells.entry(key).or_default().push(b);
+        }
+    }
+
+    fn intersects(&self, b: &LabelBox) -> bool {
+        self.keys(b)
+            .filter_map(|key| self.cells.get(&key))
+            .any(|boxes| boxes.iter().any(|other| other.intersects(b)))
+    }
+}
+
+/// Centre of each label, or `None` for the ones hidden. Labels keep `gap`
+/// away from their dot, never cover another dot or label, and stay within
+/// `bounds`.
+pub fn place_labels(labels: &[LabelRequest], bounds: LabelBox, gap: f32) -> Vec<Option<Point>> {
+    let mut order: Vec<usize> = (0..labels.len()).collect();
+    order.sort_by(|&a, &b| labels[b].importance.total_cmp(&labels[a].importance));
+
+    let largest = labels.iter().fold(Point::new(gap, gap), |size, l| {
+        Point::new(size.x.max(l.size.x), size.y.max(l.size.y))
+    });
+    let mut dots = BoxGrid::new(largest);
+    for label in labels {
+        dots.insert(LabelBox::from_center(label.anchor, Point::new(gap, gap)));
+    }
+    let mut placed = BoxGrid::new(largest);
+    let mut centers = vec![None; labels.len()];
+    for index in order {
+        let label = &labels[index];
+        let found = CANDIDATES.iter().find_map(|&(dx, dy)| {
+            let center = Point::new(
+                label.anchor.x + dx * (label.size.x / 2. + gap),
+                label.anchor.y + dy * (label.size.y / 2. + gap),
+            );
+            let candidate = LabelBox::from_center(center, label.size);
+            let free = bounds.contains(&candidate)
+                && !placed.intersects(&candidate)
+                && !dots.intersects(&candidate);
+            free.then_some(candidate)
+        });
+        if let Some(found) = found {
+            centers[index] = Some(found.center());
+            placed.insert(found);
+        }
+    }
+    centers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(x: f32, y: f32, importance: f32) -> LabelRequest {
+        LabelRequest {
+            anchor: Point::new(x, y),
+            size: Point::new(40., 10.),
+            importance,
+        }
+    }
+
+    #[test]
+    fn test_place_labels() {
+        let bounds = LabelBox::from_center(Point::default(), Point::new(200., 200.));
+        let labels = [
+            // Its preferred place is taken by the next, more important, label
+            label(0., 0., 1.),
+            label(0., 5., 5.),
+            // Against the right edge
+            label(95., -50., 1.),
+        ];
+        let centers = place_labels(&labels, bounds, 2.);
+
+        assert_eq!(centers[1], Some(Point::new(22., 12.)));
+        let first = centers[0].unwrap();
+        assert!(first.y < 0., "pushed below: {first:?}");
+        assert!(centers[2].unwrap().x < 95., "kept on the map");
+    }
+
+    #[test]
+    fn test_crowded_labels_are_hidden() {
+        let bounds = LabelBox::from_center(Point::default(), Point::new(1000., 1000.));
+        let labels: Vec<_> = (0..20).map(|i| label(0., 0., i as f32)).collect();
+        let centers = place_labels(&labels, bounds, 2.);
+        let shown: Vec<_> = (0..20).filter(|&i| centers[i].is_some()).collect();
+        // Every candidate place is taken by then, the most important win
+        assert!(shown.len() < 20);
+        assert!(shown.iter().all(|&i| i >= 20 - shown.len()), "{shown:?}");
+    }
+
+    #[test]
+    fn test_many_labels_do_not_overlap() {
+        let bounds = LabelBox::from_center(Point::default(), Point::new(1000., 1000.));
+        // Scattered dots, some of them close together
+        let labels: Vec<_> = (0..500)
+            .map(|i| {
+                label(
+                    (i * 37 % 960) as f32 - 480.,
+                    (i * 91 % 960) as f32 - 480.,
+                    1.,
+                )
+            })
+            .collect();
+        let boxes: Vec<LabelBox> = place_labels(&labels, bounds, 2.)
+            .into_iter()
+            .zip(&labels)
+            .filter_map(|(center, l)| Some(LabelBox::from_center(center?, l.size)))
+            .collect();
+        assert!(boxes.len() > 100, "{}", boxes.len());
+        for (i, a) in boxes.iter().enumerate() {
+            assert!(boxes[i + 1..].iter().all(|b| !a.intersects(b)));
+            assert!(
+                labels.iter().all(|l| {
+                    !LabelBox::from_center(l.anchor, Point::new(2., 2.)).intersects(a)
+                })
+            );
+        }
+    }
+}
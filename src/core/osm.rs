@@ -0,0 +1,323 @@
+//! Building the city database from a saved OpenStreetMap export, either an
+//! Overpass JSON answer or an OSM XML file.
+
+use std::collections::BTreeMap;
+
+use super::error::GeoError;
+
+/// A node of the export, with its tags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OsmNode {
+    pub id: u64,
+    pub lat: f64,
+    pub lon: f64,
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Format of an export, guessed from its first character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OsmFormat {
+    OverpassJson,
+    Xml,
+}
+
+impl OsmFormat {
+    pub fn detect(content: &str) -> Option<Self> {
+        match content.trim_start().chars().next()? {
+            '{' => Some(OsmFormat::OverpassJson),
+            '<' => Some(OsmFormat::Xml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct OverpassAnswer {
+    elements: Vec<OverpassElement>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct OverpassElement {
+    #[serde(rename = "type")]
+    kind: String,
+    id: u64,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+}
+
+/// Nodes of an export of any [`OsmFormat`]. Ways and relations are skipped.
+pub fn parse_export(content: &str) -> Result<Vec<OsmNode>, GeoError> {
+    match OsmFormat::detect(content) {
+        Some(OsmFormat::OverpassJson) => parse_overpass_json(content),
+        Some(OsmFormat::Xml) => parse_osm_xml(content),
+        None => Err(GeoError::DataLoading(
+            "neither Overpass JSON nor OSM XML".into(),
+        )),
+    }
+}
+
+pub fn parse_overpass_json(content: &str) -> Result<Vec<OsmNode>, GeoError> {
+    let answer: OverpassAnswer =
+        serde_json::from_str(content).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    let nodes = answer
+        .elements
+        .into_iter()
+        .filter(|e| e.kind == "node")
+        .filter_map(|e| {
+            Some(OsmNode {
+                id: e.id,
+                lat: e.lat?,
+                lon: e.lon?,
+                tags: e.tags,
+            })
+        })
+        .collect();
+    Ok(nodes)
+}
+
+pub fn parse_osm_xml(content: &str) -> Result<Vec<OsmNode>, GeoError> {
+    let doc =
+        roxmltree::Document::parse(content).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+    let mut nodes = Vec::new();
+    for node in doc.descendants().filter(|n| n.has_tag_name("node")) {
+        let attribute = |name| {
+            node.attribute(name)
+                .ok_or_else(|| GeoError::DataLoading(format!("node without {name}")))
+        };
+        let number = |name| {
+            attribute(name)?
+                .parse::<f64>()
+                .map_err(|e| GeoError::DataLoading(format!("node {name}: {e}")))
+        };
+        let id = attribute("id")?
+            .parse()
+            .map_err(|e| GeoError::DataLoading(format!("node id: {e}")))?;
+        let tags = node
+            .children()
+            .filter(|t| t.has_tag_name("tag"))
+            .filter_map(|t| Some((t.attribute("k")?.to_string(), t.attribute("v")?.to_string())))
+            .collect();
+        nodes.push(OsmNode {
+            id,
+            lat: number("lat")?,
+            lon: number("lon")?,
+            tags,
+        });
+    }
+    Ok(nodes)
+}
+
+/// Which nodes make it into the database.
+#[derive(Debug, Clone)]
+pub struct BuildRules {
+    /// Accepted values of the `place` tag.
+    pub places: Vec<String>,
+    /// Nodes without a population are kept.
+    pub min_population: Option<u64>,
+}
+
+impl Default for BuildRules {
+    /// Cities and towns, as the online query used to fetch.
+    fn default() -> Self {
+        Self {
+            places: vec!["city".into(), "town".into()],
+            min_population: None,
+        }
+    }
+}
+
+impl BuildRules {
+    pub fn accepts(&self, node: &OsmNode) -> bool {
+        let place = node.tags.get("place");
+        let population = node
+            .tags
+            .get("population")
+            .and_then(|p| parse_population(p));
+        node.tags.contains_key("name")
+            && place.is_some_and(|place| self.places.contains(place))
+            && match (self.min_population, population) {
+                (Some(min), Some(population)) => population >= min,
+                _ => true,
+            }
+    }
+}
+
+/// A city as written to the database, read back by `load_database`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DatabaseEntry {
+    #[serde(rename = "name:default")]
+    pub name_default: String,
+    #[serde(rename = "name:fr")]
+    pub name_fr: Option<String>,
+    #[serde(rename = "name:nl")]
+    pub name_nl: Option<String>,
+    #[serde(rename = "name:de")]
+    pub name_de: Option<String>,
+    #[serde(rename = "name:en")]
+    pub name_en: Option<String>,
+    /// Every other `name:*` tag.
+    #[serde(flatten)]
+    pub other_names: BTreeMap<String, String>,
+    pub id: u64,
+    pub place: Option<String>,
+    pub population: Option<u64>,
+    pub capital: Option<String>,
+    pub admin_level: Option<String>,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl From<OsmNode> for DatabaseEntry {
+    fn from(mut node: OsmNode) -> Self {
+        let mut take = |key: &str| node.tags.remove(key);
+        let name_default = take("name").unwrap_or_default();
+        let name_fr = take("name:fr");
+        let name_nl = take("name:nl");
+        let name_de = take("name:de");
+        let name_en = take("name:en");
+        let place = take("place");
+        let population = take("population").and_then(|p| parse_population(&p));
+        let capital = take("capital");
+        let admin_level = take("admin_level");
+        let other_names = node
+            .tags
+            .into_iter()
+            .filter(|(key, _)| key.starts_with("name:"))
+            .collect();
+        Self {
+            name_default,
+            name_fr,
+            name_nl,
+            name_de,
+            name_en,
+            other_names,
+            id: node.id,
+            place,
+            population,
+            capital,
+            admin_level,
+            lat: node.lat,
+            lon: node.lon,
+        }
+    }
+}
+
+/// Database entries of the accepted nodes, ordered by id so rebuilding from
+/// the same export gives the same file.
+pub fn build_database(nodes: Vec<OsmNode>, rules: &BuildRules) -> Vec<DatabaseEntry> {
+    let mut entries: Vec<DatabaseEntry> = nodes
+        .into_iter()
+        .filter(|node| rules.accepts(node))
+        .map(DatabaseEntry::from)
+        .collect();
+    entries.sort_by_key(|e| e.id);
+    entries
+}
+
+/// `population` tags are free text, such as `12 345`, `12,345` or `12.345`.
+/// Separators only split groups of three digits, anything else is refused;
+/// a decimal part, as in `1.5`, is dropped.
+fn parse_population(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let integer = match value.rfind(['.', ',']) {
+        Some(i) if value.len() - i - 1 != 3 => &value[..i],
+        _ => value,
+    };
+    let groups: Vec<&str> = integer.split([' ', ',', '.', '\'']).collect();
+    if let [first, rest @ ..] = &groups[..]
+        && !rest.is_empty()
+        && (!(1..=3).contains(&first.len()) || rest.iter().any(|g| g.len() != 3))
+    {
+        return None;
+    }
+    groups.concat().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Language, dataset::parse_database};
+
+    const JSON: &str = r#"{
+        "version": 0.6,
+        "elements": [
+            {"type": "node", "id": 2, "lat": 50.45, "lon": 3.95,
+             "tags": {"name": "Mons", "name:nl": "Bergen", "name:it": "Mons",
+                      "place": "city", "population": "95 299", "capital": "6"}},
+            {"type": "node", "id": 1, "lat": 50.6, "lon": 3.78,
+             "tags": {"name": "Ath", "place": "town"}},
+            {"type": "node", "id": 3, "lat": 50.5, "lon": 4.0,
+             "tags": {"name": "Havré", "place": "village"}},
+            {"type": "way", "id": 4, "nodes": [1, 2]}
+        ]
+    }"#;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <osm version="0.6">
+          <node id="2" lat="50.45" lon="3.95">
+            <tag k="name" v="Mons"/>
+            <tag k="name:nl" v="Bergen"/>
+            <tag k="place" v="city"/>
+          </node>
+          <node id="5" lat="50.4" lon="3.9"/>
+        </osm>"#;
+
+    #[test]
+    fn test_parse_exports() {
+        let json = parse_export(JSON).unwrap();
+        assert_eq!(json.len(), 3);
+        let xml = parse_export(XML).unwrap();
+        assert_eq!(xml.len(), 2);
+        assert_eq!(xml[0].id, json[0].id);
+        assert_eq!(xml[0].tags["name:nl"], "Bergen");
+        assert!(parse_export("id;name").is_err());
+    }
+
+    #[test]
+    fn test_build_rules() {
+        let nodes = parse_overpass_json(JSON).unwrap();
+        let entries = build_database(nodes.clone(), &BuildRules::default());
+        let ids: Vec<_> = entries.iter().map(|e| e.id).collect();
+        assert_eq!(ids, [1, 2]);
+        assert_eq!(entries[1].population, Some(95_299));
+        assert_eq!(entries[1].other_names["name:it"], "Mons");
+
+        let rules = BuildRules {
+            places: vec!["city".into(), "town".into(), "village".into()],
+            min_population: Some(1000),
+        };
+        let ids: Vec<_> = build_database(nodes, &rules).iter().map(|e| e.id).collect();
+        // Ath and Havré have no population and are kept
+        assert_eq!(ids, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_built_database_loads() {
+        let entries = build_database(parse_export(JSON).unwrap(), &BuildRules::default());
+        let json = serde_json::to_string(&entries).unwrap();
+        let cities = parse_database(&json).unwrap();
+        assert_eq!(cities[1].name(Language::Nl), "Bergen");
+        assert_eq!(cities[1].capital, Some(6));
+    }
+
+    #[test]
+    fn test_parse_population() {
+        for (value, expected) in [
+            ("95299", Some(95_299)),
+            ("95 299", Some(95_299)),
+            ("1,234,567", Some(1_234_567)),
+            ("1.234.567", Some(1_234_567)),
+            ("12'345", Some(12_345)),
+            ("1.5", Some(1)),
+            ("12,5", Some(12)),
+            ("12 345,6", Some(12_345)),
+            ("12 34", None),
+            ("1234.567", None),
+            ("about 300", None),
+        ] {
+            assert_eq!(parse_population(value), expected, "{value}");
+        }
+    }
+}
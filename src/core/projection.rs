@@ -0,0 +1,156 @@
+use std::f32::consts::PI;
+
+/// Position on the map, in the units of `belgium_map.svg` with the origin
+/// at its center and y pointing up.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance(self, other: Point) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// Convert geographic coordinates (longitude, latitude) in degrees
+/// into Mercator projection coordinates (radians-based, unscaled).
+fn mercator(long: f32, lat: f32) -> (f32, f32) {
+    // Constant: central longitude (4.5° in radians)
+    const X0: f32 = 4.5_f32.to_radians();
+
+    let x = long.to_radians();
+    let y = lat.to_radians();
+
+    let new_x = x - X0;
+    let new_y = (0.25 * PI + 0.5 * y).tan().ln();
+
+    (new_x, new_y)
+}
+
+/// Inverse of [`mercator`], back to longitude and latitude in degrees.
+fn inverse_mercator(x: f32, y: f32) -> (f32, f32) {
+    const X0: f32 = 4.5_f32.to_radians();
+
+    let long = x + X0;
+    let lat = 2. * y.exp().atan() - 0.5 * PI;
+
+    (long.to_degrees(), lat.to_degrees())
+}
+
+const X_SCALE: f32 = 11_173.297;
+const X_OFFSET: f32 = 1.215_384_8;
+const Y_SCALE: f32 = 10_760.031;
+const Y_OFFSET: f32 = -11_030.805;
+
+// linear transformation of the x coordinates
+const fn x_transformation(x: f32) -> f32 {
+    X_SCALE * x + X_OFFSET
+}
+
+// linear transformation of the y coordinates
+const fn y_transformation(y: f32) -> f32 {
+    Y_SCALE * y + Y_OFFSET
+}
+
+/// A place whose position was measured on `belgium_map.svg`, to check the
+/// projection against.
+#[derive(Debug, Clone, Copy)]
+pub struct ControlPoint {
+    pub name: &'static str,
+    pub long: f32,
+    pub lat: f32,
+    /// Where the place is drawn on the map.
+    pub map: Point,
+}
+
+pub const CONTROL_POINTS: [ControlPoint; 1] = [ControlPoint {
+    name: "Soignies",
+    long: 4.068_560_4,
+    lat: 50.579_203,
+    map: Point::new(-83., 14.),
+}];
+
+/// Convert (longitude, latitude) in degrees to map coordinates.
+pub fn oms_to_local(long: f32, lat: f32) -> (f32, f32) {
+    let (x, y) = mercator(long, lat);
+    (x_transformation(x), y_transformation(y))
+}
+
+/// Convert map coordinates back to (longitude, latitude) in degrees.
+pub fn local_to_oms(x: f32, y: f32) -> (f32, f32) {
+    inverse_mercator((x - X_OFFSET) / X_SCALE, (y - Y_OFFSET) / Y_SCALE)
+}
+
+/// Great-circle distance in kilometers between two points of the map.
+pub fn distance_km(a: Point, b: Point) -> f32 {
+    const EARTH_RADIUS_KM: f32 = 6_371.;
+
+    let (long_a, lat_a) = local_to_oms(a.x, a.y);
+    let (long_b, lat_b) = local_to_oms(b.x, b.y);
+    let d_lat = (lat_b - lat_a).to_radians();
+    let d_long = (long_b - long_a).to_radians();
+    let h = (0.5 * d_lat).sin().powi(2)
+        + lat_a.to_radians().cos() * lat_b.to_radians().cos() * (0.5 * d_long).sin().powi(2);
+    2. * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oms_to_local_x() {
+        for point in CONTROL_POINTS {
+            let (x, y) = oms_to_local(point.long, point.lat);
+            assert!(
+                (x - point.map.x).abs() < 1.0,
+                "{} X: expected ≈ {}, obtained {}",
+                point.name,
+                point.map.x,
+                x
+            );
+            assert!(
+                (y - point.map.y).abs() < 1.0,
+                "{} Y: expected ≈ {}, obtained {}",
+                point.name,
+                point.map.y,
+                y
+            );
+        }
+
+        // Liège
+        // let x_oms_liege = 5.5736112_f32;
+        // let expected_liege = 216.0_f32;
+        // let result_liege = oms_to_local_x(x_oms_liege);
+        // assert!(
+        //     (result_liege - expected_liege).abs() < 1.0,
+        //     "Liège: expected ≈ {}, obtained {}",
+        //     expected_liege,
+        //     result_liege
+        // );
+    }
+
+    #[test]
+    fn test_local_to_oms_roundtrip() {
+        let (long, lat) = (5.573_611_f32, 50.645_138_f32);
+        let (x, y) = oms_to_local(long, lat);
+        let (long_back, lat_back) = local_to_oms(x, y);
+        assert!((long - long_back).abs() < 1e-3, "{long} != {long_back}");
+        assert!((lat - lat_back).abs() < 1e-3, "{lat} != {lat_back}");
+    }
+
+    #[test]
+    fn test_distance_km() {
+        // Brussels - Antwerp is about 41 km
+        let (bx, by) = oms_to_local(4.351_7, 50.846_7);
+        let (ax, ay) = oms_to_local(4.399_7, 51.221_1);
+        let distance = distance_km(Point::new(bx, by), Point::new(ax, ay));
+        assert!((distance - 41.8).abs() < 1.5, "obtained {distance}");
+    }
+}
@@ -0,0 +1,753 @@
+use std::collections::HashSet;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use super::{
+    city::{CityId, CityRecord, Place},
+    error::QuizError,
+    hints::{HINT_CIRCLE_KM, Hint, MAX_HINTS, max_points_with_hints},
+    i18n::Language,
+    projection::{Point, distance_km},
+    regions::{Outline, ProvinceMap, Region},
+    scoring::{Score, ScoringCurve},
+    selection::{SelectionStrategy, Weighted},
+    spatial::CityIndex,
+};
+
+/// Default number of cities to guess in a round.
+pub const ROUND_LENGTH: usize = 10;
+
+/// Which cities are asked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    #[default]
+    All,
+    /// Provincial capitals and cities.
+    Easy,
+    /// Towns.
+    Medium,
+    /// Villages.
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::All,
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::All => "all",
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|d| d.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Cities of unknown kind count as towns.
+    pub fn includes(self, city: &CityRecord) -> bool {
+        let tier = match city.place {
+            _ if city.is_major_capital() => Difficulty::Easy,
+            Some(Place::City) => Difficulty::Easy,
+            Some(Place::Town) | None => Difficulty::Medium,
+            Some(Place::Village) => Difficulty::Hard,
+        };
+        self == Difficulty::All || self == tier
+    }
+}
+
+/// Every city of the dataset and the ones left to guess in the round.
+#[derive(Debug)]
+pub struct GuessSet {
+    all_cities: Vec<CityRecord>,
+    pub to_guess: Vec<CityRecord>,
+    round_length: usize,
+    difficulty: Difficulty,
+    /// Cities to draw from, such as a study set, instead of all of them.
+    pool: Option<HashSet<CityId>>,
+    /// Locations of `all_cities`.
+    index: CityIndex,
+    strategy: Box<dyn SelectionStrategy>,
+    rng: StdRng,
+}
+
+impl GuessSet {
+    pub fn new(all_cities: Vec<CityRecord>) -> Self {
+        let index = CityIndex::new(all_cities.iter().map(|c| c.loc).collect());
+        let mut set = Self {
+            all_cities,
+            to_guess: Vec::new(),
+            round_length: ROUND_LENGTH,
+            difficulty: Difficulty::default(),
+            pool: None,
+            index,
+            strategy: Box::new(Weighted),
+            rng: StdRng::from_os_rng(),
+        };
+        // Reported by `Quiz::start_round`
+        set.load_next().ok();
+        set
+    }
+
+    pub fn all_cities(&self) -> &[CityRecord] {
+        &self.all_cities
+    }
+
+    pub fn round_length(&self) -> usize {
+        self.round_length
+    }
+
+    /// Locations of [`GuessSet::all_cities`], in the same order.
+    pub fn index(&self) -> &CityIndex {
+        &self.index
+    }
+
+    /// City of the dataset closest to `point` on the map.
+    pub fn nearest_city(&self, point: Point) -> Option<&CityRecord> {
+        self.index.nearest(point).map(|i| &self.all_cities[i])
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Draw the cities of the next round, failing when neither the
+    /// difficulty nor the pool leave any.
+    pub fn load_next(&mut self) -> Result<(), QuizError> {
+        self.to_guess = self.pick();
+        if self.to_guess.is_empty() && self.round_length > 0 {
+            return Err(QuizError::EmptyPool(self.difficulty));
+        }
+        Ok(())
+    }
+
+    /// Distinct cities of the difficulty and the pool, drawn by the
+    /// strategy.
+    fn pick(&mut self) -> Vec<CityRecord> {
+        let pool: Vec<&CityRecord> = self
+            .all_cities
+            .iter()
+            .filter(|c| self.difficulty.includes(c))
+            .filter(|c| self.pool.as_ref().is_none_or(|pool| pool.contains(&c.id)))
+            .collect();
+        let amount = self.round_length.min(pool.len());
+        self.strategy.select(&pool, amount, &mut self.rng)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Location(Point),
+    /// Any name of the city.
+    Name(String),
+    /// Name of the city in the given language.
+    Translation(Language, String),
+    /// Id or name of the province the city is in.
+    Province(String),
+    /// Region the city is in.
+    Region(Region),
+}
+
+/// Distance between a location answer and the city.
+#[derive(Debug, Clone, Copy)]
+pub struct Distance {
+    /// In map units, as used by the [`ScoringCurve`].
+    pub map: f32,
+    pub km: f32,
+}
+
+/// Known places around a location answer, to tell the player where they
+/// actually clicked.
+#[derive(Debug, Clone)]
+pub struct Surroundings {
+    pub nearest: Option<CityRecord>,
+    /// Name of the province the answer is in.
+    pub province: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuessResult {
+    pub city: CityRecord,
+    pub points: u32,
+    /// Only set for [`Answer::Location`].
+    pub distance: Option<Distance>,
+    /// Only set for [`Answer::Location`].
+    pub surroundings: Option<Surroundings>,
+    /// The guess got the [`ScoringCurve::province_bonus`].
+    pub province_bonus: bool,
+    /// Hints taken before answering.
+    pub hints: u32,
+}
+
+/// A game: rounds of questions, answers and the score.
+#[derive(Debug)]
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+pub struct Quiz {
+    guess_set: GuessSet,
+    curve: ScoringCurve,
+    score: Score,
+    current: Option<CityRecord>,
+    /// Hints given for the current city.
+    hints: u32,
+    /// Kinds of hint tried for the current city, given or not available.
+    hint_step: u32,
+    provinces: ProvinceMap,
+    /// Border of the provinces, computed once.
+    outline: Outline,
+    /// Region answers tell the German-speaking community apart.
+    german_speaking: bool,
+}
+
+impl Quiz {
+    pub fn new(cities: Vec<CityRecord>) -> Self {
+        Self {
+            guess_set: GuessSet::new(cities),
+            curve: ScoringCurve::default(),
+            score: Score::default(),
+            current: None,
+            hints: 0,
+            hint_step: 0,
+            provinces: ProvinceMap::default(),
+            outline: Outline::default(),
+            german_speaking: false,
+        }
+    }
+
+    /// Same seed, same questions.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.guess_set.rng = StdRng::seed_from_u64(seed);
+        self.guess_set.load_next().ok();
+        self
+    }
+
+    pub fn with_round_length(mut self, round_length: usize) -> Self {
+        self.guess_set.round_length = round_length;
+        self.guess_set.load_next().ok();
+        self
+    }
+
+    /// Rounds are empty without any city of `difficulty`, see
+    /// [`Quiz::set_difficulty`].
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.set_difficulty(difficulty).ok();
+        self
+    }
+
+    /// Only draw cities of `difficulty`. The cities left in the round are
+    /// drawn again, failing if there are none.
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) -> Result<(), QuizError> {
+        self.guess_set.difficulty = difficulty;
+        self.guess_set.load_next()
+    }
+
+    pub fn with_pool(mut self, pool: Option<HashSet<CityId>>) -> Self {
+        self.set_pool(pool).ok();
+        self
+    }
+
+    /// Only draw cities of `pool`, all of them if `None`. The cities left in
+    /// the round are drawn again, failing if there are none.
+    pub fn set_pool(&mut self, pool: Option<HashSet<CityId>>) -> Result<(), QuizError> {
+        self.guess_set.pool = pool;
+        self.guess_set.load_next()
+    }
+
+    /// How the cities of a round are drawn, [`Weighted`] by default.
+    pub fn with_selection(mut self, strategy: Box<dyn SelectionStrategy>) -> Self {
+        self.guess_set.strategy = strategy;
+        self.guess_set.load_next().ok();
+        self
+    }
+
+    pub fn with_curve(mut self, curve: ScoringCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Needed to answer with [`Answer::Province`].
+    pub fn with_provinces(mut self, provinces: ProvinceMap) -> Self {
+        self.outline = provinces.outline();
+        self.provinces = provinces;
+        self
+    }
+
+    pub fn provinces(&self) -> &ProvinceMap {
+        &self.provinces
+    }
+
+    pub fn outline(&self) -> &Outline {
+        &self.outline
+    }
+
+    /// Whether a location answer is in the country. Any location is, without
+    /// the provinces.
+    pub fn accepts_location(&self, point: Point) -> bool {
+        self.provinces.provinces.is_empty() || self.provinces.in_country(point)
+    }
+
+    /// Region answers expect [`Region::GermanSpeaking`] for the cities of
+    /// the German-speaking community instead of Wallonia.
+    pub fn with_german_speaking(mut self, german_speaking: bool) -> Self {
+        self.german_speaking = german_speaking;
+        self
+    }
+
+    /// Regions to choose from with [`Answer::Region`].
+    pub fn regions(&self) -> &'static [Region] {
+        if self.german_speaking {
+            &Region::WITH_GERMAN_SPEAKING
+        } else {
+            &Region::ALL
+        }
+    }
+
+    /// Region expected for `city`, from the province it is in.
+    pub fn region_of(&self, city: &CityRecord) -> Option<Region> {
+        self.provinces
+            .province_of(city.loc)
+            .and_then(|p| p.region_of_city(city, self.german_speaking))
+    }
+
+    /// Draw new cities and reset the score.
+    pub fn start_round(&mut self) -> Result<(), QuizError> {
+        self.score = Score::default();
+        self.current = None;
+        self.guess_set.load_next()
+    }
+
+    /// Move to the next city of the round, `None` once the round is over.
+    pub fn next_question(&mut self) -> Option<&CityRecord> {
+        self.current = self.guess_set.to_guess.pop();
+        self.hints = 0;
+        self.hint_step = 0;
+        if let Some(city) = &self.current {
+            self.guess_set.strategy.asked(city);
+        }
+        self.current.as_ref()
+    }
+
+    /// City waiting for an answer.
+    pub fn current(&self) -> Option<&CityRecord> {
+        self.current.as_ref()
+    }
+
+    pub fn submit(&mut self, answer: &Answer) -> Result<GuessResult, QuizError> {
+        let city = self.current.take().ok_or(QuizError::NoQuestion)?;
+        let surroundings = match answer {
+            Answer::Location(guess) => Some(self.surroundings(*guess)),
+            _ => None,
+        };
+        let mut province_bonus = false;
+        let (mut points, distance) = match answer {
+            Answer::Location(guess) => {
+                let map = guess.distance(city.loc);
+                let km = distance_km(*guess, city.loc);
+                let mut points = self.curve.points(map);
+                let province = self.provinces.province_of(city.loc).map(|p| &p.id);
+                if province.is_some()
+                    && self.provinces.province_of(*guess).map(|p| &p.id) == province
+                    && points < self.curve.max
+                {
+                    province_bonus = true;
+                    points = (points + self.curve.province_bonus).min(self.curve.max);
+                }
+                (points, Some(Distance { map, km }))
+            }
+            Answer::Name(name) => (self.all_or_nothing(city.matches_name(name)), None),
+            Answer::Translation(language, name) => {
+                let expected = city.name(*language).trim().to_lowercase();
+                (
+                    self.all_or_nothing(expected == name.trim().to_lowercase()),
+                    None,
+                )
+            }
+            Answer::Province(province) => {
+                let province = province.trim();
+                let correct = self.provinces.province_of(city.loc).is_some_and(|p| {
+                    p.id.eq_ignore_ascii_case(province) || p.name.eq_ignore_ascii_case(province)
+                });
+                (self.all_or_nothing(correct), None)
+            }
+            Answer::Region(region) => {
+                let correct = self.region_of(&city) == Some(*region);
+                (self.all_or_nothing(correct), None)
+            }
+        };
+        points = points.min(max_points_with_hints(self.curve.max, self.hints));
+        self.score.total += points;
+        self.score.max += self.curve.max;
+        self.score.province_bonuses += u32::from(province_bonus);
+        self.score.hints += self.hints;
+        Ok(GuessResult {
+            city,
+            points,
+            distance,
+            surroundings,
+            province_bonus,
+            hints: self.hints,
+        })
+    }
+
+    /// Nearest city and province of a point of the map.
+    pub fn surroundings(&self, point: Point) -> Surroundings {
+        Surroundings {
+            nearest: self.guess_set.nearest_city(point).cloned(),
+            province: self.provinces.province_of(point).map(|p| p.name.clone()),
+        }
+    }
+
+    /// Next hint about the current city: its province, its region, then a
+    /// circle around it. Hints needing the provinces are skipped without
+    /// them.
+    pub fn hint(&mut self) -> Option<Hint> {
+        let city = self.current.as_ref()?;
+        let province = self.provinces.province_of(city.loc);
+        while self.hint_step < MAX_HINTS {
+            self.hint_step += 1;
+            let hint = match self.hint_step {
+                1 => province.map(|p| Hint::Province(p.name.clone())),
+                2 => province.and_then(|p| p.region()).map(Hint::Region),
+                _ => {
+                    // Map units per km around the city
+                    let east = Point::new(city.loc.x + 1., city.loc.y);
+                    let radius = HINT_CIRCLE_KM / distance_km(city.loc, east);
+                    let angle = self.guess_set.rng.random_range(0.0..std::f32::consts::TAU);
+                    let offset = self.guess_set.rng.random_range(0.0..0.7) * radius;
+                    Some(Hint::Circle {
+                        center: Point::new(
+                            city.loc.x + offset * angle.cos(),
+                            city.loc.y + offset * angle.sin(),
+                        ),
+                        radius,
+                    })
+                }
+            };
+            if hint.is_some() {
+                self.hints += 1;
+                return hint;
+            }
+        }
+        None
+    }
+
+    /// Hints given for the current city.
+    pub fn hints(&self) -> u32 {
+        self.hints
+    }
+
+    fn all_or_nothing(&self, correct: bool) -> u32 {
+        if correct { self.curve.max } else { 0 }
+    }
+
+    pub fn score(&self) -> Score {
+        self.score
+    }
+
+    pub fn curve(&self) -> &ScoringCurve {
+        &self.curve
+    }
+
+    pub fn guess_set(&self) -> &GuessSet {
+        &self.guess_set
+    }
+
+    /// Cities left after the current one.
+    pub fn remaining(&self) -> usize {
+        self.guess_set.to_guess.len()
+    }
+
+    pub fn is_round_over(&self) -> bool {
+        self.current.is_none() && self.remaining() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::load_database;
+
+    fn quiz() -> Quiz {
+        Quiz::new(load_database("database/belgium_cities.json").unwrap())
+    }
+
+    #[test]
+    fn test_round_of_exact_guesses() {
+        let mut quiz = quiz();
+        let mut asked = 0;
+        while let Some(city) = quiz.next_question() {
+            let answer = Answer::Location(city.loc);
+            let result = quiz.submit(&answer).unwrap();
+            assert_eq!(result.points, 100);
+            asked += 1;
+        }
+        assert_eq!(asked, ROUND_LENGTH);
+        assert!(quiz.is_round_over());
+        assert_eq!(
+            quiz.score(),
+            Score {
+                total: 1000,
+                max: 1000,
+                ..Default::default()
+            }
+        );
+
+        quiz.start_round().unwrap();
+        assert_eq!(quiz.score(), Score::default());
+        assert_eq!(quiz.remaining(), ROUND_LENGTH);
+    }
+
+    #[test]
+    fn test_name_answer() {
+        let mut quiz = quiz();
+        let name = quiz.next_question().unwrap().names.default.to_uppercase();
+        let result = quiz.submit(&Answer::Name(name)).unwrap();
+        assert_eq!(result.points, 100);
+
+        quiz.next_question();
+        let result = quiz.submit(&Answer::Name("Atlantis".into())).unwrap();
+        assert_eq!(result.points, 0);
+    }
+
+    #[test]
+    fn test_translation_and_province_answers() {
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let cities: Vec<_> = load_database("database/belgium_cities.json")
+            .unwrap()
+            .into_iter()
+            .filter(|c| c.names.fr.as_deref() == Some("Mons"))
+            .collect();
+        let mut quiz = Quiz::new(cities).with_provinces(provinces);
+
+        quiz.next_question();
+        let fr = Answer::Translation(Language::Fr, "mons".into());
+        assert_eq!(quiz.submit(&fr).unwrap().points, 100);
+
+        quiz.start_round().unwrap();
+        quiz.next_question();
+        let nl = Answer::Translation(Language::Nl, "Mons".into());
+        assert_eq!(quiz.submit(&nl).unwrap().points, 0);
+
+        quiz.start_round().unwrap();
+        quiz.next_question();
+        let province = Answer::Province("hainaut".into());
+        assert_eq!(quiz.submit(&province).unwrap().points, 100);
+    }
+
+    #[test]
+    fn test_seeded_rounds() {
+        let names = |quiz: Quiz| -> Vec<String> {
+            let quiz = quiz.with_round_length(5).with_seed(42);
+            let set = &quiz.guess_set().to_guess;
+            set.iter().map(|c| c.names.default.clone()).collect()
+        };
+        let first = names(quiz());
+        assert_eq!(first.len(), 5);
+        assert_eq!(first, names(quiz()));
+    }
+
+    #[test]
+    fn test_difficulty_and_weights() {
+        let json = r#"[
+            {"name:default": "Bruxelles", "lat": 50.85, "lon": 4.35, "place": "city",
+             "population": 1200000, "capital": "yes"},
+            {"name:default": "Ath", "lat": 50.63, "lon": 3.78, "place": "town"},
+            {"name:default": "Havré", "lat": 50.46, "lon": 4.04, "place": "village",
+             "population": 3000},
+            {"name:default": "Mons", "lat": 50.45, "lon": 3.95, "place": "town",
+             "capital": "6"}
+        ]"#;
+        let cities = crate::core::parse_database(json).unwrap();
+        let names = |difficulty| -> Vec<String> {
+            let quiz = Quiz::new(cities.clone()).with_difficulty(difficulty);
+            let mut names: Vec<_> = quiz
+                .guess_set()
+                .to_guess
+                .iter()
+                .map(|c| c.names.default.clone())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(Difficulty::Easy), ["Bruxelles", "Mons"]);
+        assert_eq!(names(Difficulty::Medium), ["Ath"]);
+        assert_eq!(names(Difficulty::Hard), ["Havré"]);
+        assert_eq!(names(Difficulty::All).len(), 4);
+
+        // Brussels outweighs the three others together
+        let mut quiz = Quiz::new(cities).with_round_length(1).with_seed(1);
+        let mut brussels = 0;
+        for _ in 0..200 {
+            quiz.start_round().unwrap();
+            if quiz.guess_set().to_guess[0].names.default == "Bruxelles" {
+                brussels += 1;
+            }
+        }
+        assert!(brussels > 120, "{brussels}");
+    }
+
+    #[test]
+    fn test_empty_difficulty() {
+        let json = r#"[
+            {"name:default": "Ath", "lat": 50.63, "lon": 3.78, "place": "town"},
+            {"name:default": "Mons", "lat": 50.45, "lon": 3.95, "place": "town"}
+        ]"#;
+        let cities = crate::core::parse_database(json).unwrap();
+        let mut quiz = Quiz::new(cities).with_difficulty(Difficulty::Hard);
+        assert_eq!(quiz.remaining(), 0);
+        assert_eq!(
+            quiz.start_round(),
+            Err(QuizError::EmptyPool(Difficulty::Hard))
+        );
+        assert_eq!(quiz.set_difficulty(Difficulty::All), Ok(()));
+        assert_eq!(quiz.remaining(), 2);
+
+        let empty = Some(HashSet::new());
+        assert_eq!(
+            quiz.set_pool(empty),
+            Err(QuizError::EmptyPool(Difficulty::All))
+        );
+    }
+
+    #[test]
+    fn test_pool() {
+        let cities = load_database("database/belgium_cities.json").unwrap();
+        let pool: HashSet<CityId> = cities.iter().take(3).map(|c| c.id).collect();
+        let quiz = Quiz::new(cities).with_pool(Some(pool.clone()));
+        let drawn = &quiz.guess_set().to_guess;
+        assert_eq!(drawn.len(), 3);
+        assert!(drawn.iter().all(|c| pool.contains(&c.id)));
+    }
+
+    #[test]
+    fn test_nearest_city() {
+        let quiz = Quiz::new(load_database("database/belgium_cities.json").unwrap());
+        let cities = quiz.guess_set().all_cities();
+        for city in &cities[..10] {
+            let near = Point::new(city.loc.x + 0.1, city.loc.y);
+            assert_eq!(quiz.guess_set().nearest_city(near).unwrap().id, city.id);
+        }
+    }
+
+    #[test]
+    fn test_surroundings() {
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let mut quiz = Quiz::new(load_database("database/belgium_cities.json").unwrap())
+            .with_provinces(provinces);
+        let city = quiz.next_question().unwrap().clone();
+        let ath = quiz
+            .guess_set()
+            .all_cities()
+            .iter()
+            .find(|c| c.names.default == "Ath")
+            .unwrap()
+            .clone();
+
+        let result = quiz
+            .submit(&Answer::Location(Point::new(ath.loc.x + 0.5, ath.loc.y)))
+            .unwrap();
+        assert_eq!(result.city.id, city.id);
+        let surroundings = result.surroundings.unwrap();
+        assert_eq!(surroundings.nearest.unwrap().id, ath.id);
+        assert_eq!(surroundings.province.as_deref(), Some("Hainaut"));
+    }
+
+    #[test]
+    fn test_province_bonus_and_hints() {
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let mut quiz = Quiz::new(load_database("database/belgium_cities.json").unwrap())
+            .with_provinces(provinces)
+            .with_seed(3);
+        let arlon = quiz
+            .guess_set()
+            .all_cities()
+            .iter()
+            .find(|c| c.names.default == "Arlon")
+            .unwrap()
+            .clone();
+        let bastogne = quiz
+            .guess_set()
+            .all_cities()
+            .iter()
+            .find(|c| c.names.default == "Bastogne")
+            .unwrap()
+            .loc;
+
+        // Far from Arlon, but in the province of Luxembourg
+        quiz.current = Some(arlon.clone());
+        let result = quiz.submit(&Answer::Location(bastogne)).unwrap();
+        assert!(result.province_bonus);
+        let far = quiz.curve().points(bastogne.distance(arlon.loc));
+        assert_eq!(result.points, far + quiz.curve().province_bonus);
+
+        quiz.current = Some(arlon.clone());
+        assert_eq!(quiz.hint(), Some(Hint::Province("Luxembourg".into())));
+        assert_eq!(quiz.hint(), Some(Hint::Region(Region::Wallonia)));
+        let Some(Hint::Circle { center, radius }) = quiz.hint() else {
+            panic!("a circle is the last hint");
+        };
+        assert!(center.distance(arlon.loc) < radius);
+        assert_eq!(quiz.hint(), None);
+        let result = quiz.submit(&Answer::Location(arlon.loc)).unwrap();
+        assert_eq!((result.points, result.hints), (25, 3));
+        assert_eq!(quiz.score().province_bonuses, 1);
+        assert_eq!(quiz.score().hints, 3);
+    }
+
+    #[test]
+    fn test_hints_without_provinces() {
+        let mut quiz = quiz();
+        let city = quiz.next_question().unwrap().clone();
+        assert!(matches!(quiz.hint(), Some(Hint::Circle { .. })));
+        assert_eq!(quiz.hint(), None);
+        assert_eq!(quiz.hints(), 1);
+        let result = quiz.submit(&Answer::Location(city.loc)).unwrap();
+        assert_eq!((result.points, result.hints), (75, 1));
+    }
+
+    #[test]
+    fn test_region_answers() {
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let mut quiz = Quiz::new(load_database("database/belgium_cities.json").unwrap())
+            .with_provinces(provinces);
+        let city = |quiz: &Quiz, name: &str| {
+            quiz.guess_set()
+                .all_cities()
+                .iter()
+                .find(|c| c.names.default == name)
+                .unwrap()
+                .clone()
+        };
+        let (gent, eupen) = (city(&quiz, "Gent"), city(&quiz, "Eupen"));
+        assert_eq!(quiz.region_of(&gent), Some(Region::Flanders));
+        assert_eq!(quiz.region_of(&eupen), Some(Region::Wallonia));
+        assert_eq!(quiz.regions().len(), 3);
+
+        quiz.current = Some(gent);
+        let result = quiz.submit(&Answer::Region(Region::Flanders)).unwrap();
+        assert_eq!(result.points, quiz.curve().max);
+
+        let mut quiz = quiz.with_german_speaking(true);
+        assert_eq!(quiz.region_of(&eupen), Some(Region::GermanSpeaking));
+        let malmedy = city(&quiz, "Malmedy");
+        assert_eq!(quiz.region_of(&malmedy), Some(Region::Wallonia));
+        quiz.current = Some(eupen);
+        let result = quiz.submit(&Answer::Region(Region::Wallonia)).unwrap();
+        assert_eq!(result.points, 0);
+    }
+
+    #[test]
+    fn test_submit_without_question() {
+        let mut quiz = quiz();
+        let answer = Answer::Location(Point::default());
+        assert_eq!(quiz.submit(&answer).unwrap_err(), QuizError::NoQuestion);
+    }
+}
@@ -0,0 +1,518 @@
+use std::path::Path;
+
+use super::{
+    city::{CityRecord, fold_name},
+    error::GeoError,
+    projection::{Point, distance_km},
+};
+
+/// Cities and guesses this close to the outline are accepted, the drawn
+/// borders being simplified.
+pub const OUTLINE_TOLERANCE_KM: f32 = 2.;
+
+/// Language region of Belgium, as encoded in the province ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    Flanders,
+    Wallonia,
+    Brussels,
+    /// East of the province of Liège, only told apart from Wallonia when
+    /// asked for, see [`Province::region_of_city`].
+    GermanSpeaking,
+}
+
+/// Municipalities of the German-speaking community and their former
+/// municipalities, all in the province of Liège.
+const GERMAN_SPEAKING: [&str; 25] = [
+    "Amel",
+    "Büllingen",
+    "Burg-Reuland",
+    "Bütgenbach",
+    "Crombach",
+    "Elsenborn",
+    "Eupen",
+    "Eynatten",
+    "Hauset",
+    "Heppenbach",
+    "Herbesthal",
+    "Hergenrath",
+    "Kelmis",
+    "Kettenis",
+    "Lommersweiler",
+    "Lontzen",
+    "Manderfeld",
+    "Meyerode",
+    "Neu-Moresnet",
+    "Raeren",
+    "Recht",
+    "Reuland",
+    "Rocherath",
+    "Sankt Vith",
+    "Schönberg",
+];
+
+impl Region {
+    pub const ALL: [Region; 3] = [Region::Flanders, Region::Wallonia, Region::Brussels];
+    pub const WITH_GERMAN_SPEAKING: [Region; 4] = [
+        Region::Flanders,
+        Region::Wallonia,
+        Region::Brussels,
+        Region::GermanSpeaking,
+    ];
+
+    /// `BE-V*` provinces are Flemish, `BE-W*` Walloon and `BE-BRU` is Brussels.
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id.strip_prefix("BE-")? {
+            "BRU" => Some(Region::Brussels),
+            code if code.starts_with('V') => Some(Region::Flanders),
+            code if code.starts_with('W') => Some(Region::Wallonia),
+            _ => None,
+        }
+    }
+}
+
+/// A province outline, made of one or more closed rings.
+#[derive(Debug, Clone)]
+pub struct Province {
+    /// ISO 3166-2 code, such as `BE-WHT`.
+    pub id: String,
+    pub name: String,
+    pub rings: Vec<Vec<Point>>,
+}
+
+impl Province {
+    pub fn region(&self) -> Option<Region> {
+        Region::from_id(&self.id)
+    }
+
+    /// Region of a city of this province, the German-speaking community
+    /// being split from Wallonia if `german_speaking`.
+    pub fn region_of_city(&self, city: &CityRecord, german_speaking: bool) -> Option<Region> {
+        let region = self.region()?;
+        let is_german_speaking = || {
+            city.names.all().map(fold_name).any(|name| {
+                GERMAN_SPEAKING
+                    .iter()
+                    .any(|german| fold_name(german) == name)
+            })
+        };
+        if german_speaking && self.id == "BE-WLG" && is_german_speaking() {
+            Some(Region::GermanSpeaking)
+        } else {
+            Some(region)
+        }
+    }
+
+    /// Segments of every ring, closing them.
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.rings.iter().flat_map(|ring| {
+            let previous = ring.iter().cycle().skip(ring.len().saturating_sub(1));
+            ring.iter().zip(previous).map(|(a, b)| (*a, *b))
+        })
+    }
+
+    /// Even-odd point in polygon test over every ring.
+    pub fn contains(&self, point: Point) -> bool {
+        let mut inside = false;
+        for ring in &self.rings {
+            let mut j = ring.len().saturating_sub(1);
+            for i in 0..ring.len() {
+                let (a, b) = (ring[i], ring[j]);
+                if (a.y > point.y) != (b.y > point.y)
+                    && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+                {
+                    inside = !inside;
+                }
+                j = i;
+            }
+        }
+        inside
+    }
+}
+
+/// Provinces read from the map, in map coordinates.
+#[derive(Debug, Clone, Default)]
+pub struct ProvinceMap {
+    /// Width and height of the map.
+    pub size: Point,
+    pub provinces: Vec<Province>,
+}
+
+impl ProvinceMap {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, GeoError> {
+        let svg = std::fs::read_to_string(path).map_err(|e| GeoError::MapLoading(e.to_string()))?;
+        Self::from_svg(&svg)
+    }
+
+    /// Read every `BE-*` path of the SVG, centered like the map drawn by
+    /// the game.
+    pub fn from_svg(svg: &str) -> Result<Self, GeoError> {
+        let root = svg
+            .find("<svg")
+            .map(|start| &svg[start..])
+            .ok_or_else(|| GeoError::MapLoading("no <svg> element".into()))?;
+        let root = &root[..root.find('>').unwrap_or(root.len())];
+        let size = |name| {
+            attribute(root, name)
+                .and_then(|value| value.trim_end_matches("px").parse::<f32>().ok())
+                .ok_or_else(|| GeoError::MapLoading(format!("missing svg {name}")))
+        };
+        let size = Point::new(size("width")?, size("height")?);
+        let half = Point::new(size.x / 2., size.y / 2.);
+
+        let mut provinces = Vec::new();
+        for element in svg.split("<path").skip(1) {
+            let element = &element[..element.find('>').unwrap_or(element.len())];
+            let Some(id) = attribute(element, "id").filter(|id| id.starts_with("BE-")) else {
+                continue;
+            };
+            let data = attribute(element, "d")
+                .ok_or_else(|| GeoError::MapLoading(format!("{id} has no path data")))?;
+            let rings = parse_path(data)
+                .map_err(|e| GeoError::MapLoading(format!("{id}: {e}")))?
+                .into_iter()
+                // SVG y points down, the map y points up
+                .map(|ring| {
+                    ring.into_iter()
+                        .map(|p| Point::new(p.x - half.x, half.y - p.y))
+                        .collect()
+                })
+                .collect();
+            provinces.push(Province {
+                id: id.to_string(),
+                name: attribute(element, "title").unwrap_or(id).to_string(),
+                rings,
+            });
+        }
+        Ok(Self { size, provinces })
+    }
+
+    pub fn province_of(&self, point: Point) -> Option<&Province> {
+        self.provinces.iter().find(|p| p.contains(point))
+    }
+
+    pub fn region_of(&self, point: Point) -> Option<Region> {
+        self.province_of(point).and_then(Province::region)
+    }
+
+    /// Closest point on the edge of any province.
+    pub fn nearest_edge_point(&self, point: Point) -> Option<Point> {
+        nearest_on_segments(point, self.provinces.iter().flat_map(Province::edges))
+    }
+
+    /// In a province, or close enough to the outline to be blamed on the
+    /// simplified borders, as the cities of the dataset.
+    pub fn in_country(&self, point: Point) -> bool {
+        self.province_of(point).is_some()
+            || self
+                .nearest_edge_point(point)
+                .is_some_and(|edge| distance_km(edge, point) <= OUTLINE_TOLERANCE_KM)
+    }
+
+    /// Border of the country, the union of the provinces: their edges not
+    /// running along another province.
+    pub fn outline(&self) -> Outline {
+        // Bounding boxes, grown by the shared edge tolerance, skip most of
+        // the provinces
+        let bounds: Vec<(Point, Point)> = self
+            .provinces
+            .iter()
+            .map(|p| {
+                p.edges().fold(
+                    (
+                        Point::new(f32::MAX, f32::MAX),
+                        Point::new(f32::MIN, f32::MIN),
+                    ),
+                    |(min, max), (a, _)| {
+                        (
+                            Point::new(min.x.min(a.x - SHARED_EDGE), min.y.min(a.y - SHARED_EDGE)),
+                            Point::new(max.x.max(a.x + SHARED_EDGE), max.y.max(a.y + SHARED_EDGE)),
+                        )
+                    },
+                )
+            })
+            .collect();
+        let mut segments = Vec::new();
+        for (index, province) in self.provinces.iter().enumerate() {
+            segments.extend(province.edges().filter(|(a, b)| {
+                let middle = Point::new((a.x + b.x) / 2., (a.y + b.y) / 2.);
+                // Neighbours are drawn separately, their borders do not
+                // exactly match
+                let shared = |(other, (min, max)): (usize, &(Point, Point))| {
+                    let p = &self.provinces[other];
+                    other != index
+                        && (min.x..=max.x).contains(&middle.x)
+                        && (min.y..=max.y).contains(&middle.y)
+                        && (p.contains(middle)
+                            || p.edges().any(|(c, d)| {
+                                closest_on_segment(middle, c, d).distance(middle) <= SHARED_EDGE
+                            }))
+                };
+                !bounds.iter().enumerate().any(shared)
+            }));
+        }
+        Outline { segments }
+    }
+}
+
+/// Edges this close to another province run along it, in map units.
+const SHARED_EDGE: f32 = 0.5;
+
+/// Border of the country, see [`ProvinceMap::outline`].
+#[derive(Debug, Clone, Default)]
+pub struct Outline {
+    pub segments: Vec<(Point, Point)>,
+}
+
+impl Outline {
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Closest point on the border, where guesses outside are snapped.
+    pub fn nearest_point(&self, point: Point) -> Option<Point> {
+        nearest_on_segments(point, self.segments.iter().copied())
+    }
+}
+
+fn nearest_on_segments(
+    point: Point,
+    segments: impl Iterator<Item = (Point, Point)>,
+) -> Option<Point> {
+    segments
+        .map(|(a, b)| closest_on_segment(point, a, b))
+        .min_by(|a, b| a.distance(point).total_cmp(&b.distance(point)))
+}
+
+fn closest_on_segment(point: Point, a: Point, b: Point) -> Point {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length = dx * dx + dy * dy;
+    if length == 0. {
+        return a;
+    }
+    let t = (((point.x - a.x) * dx + (point.y - a.y) * dy) / length).clamp(0., 1.);
+    Point::new(a.x + t * dx, a.y + t * dy)
+}
+
+/// Value of `name="..."` in an element's attributes.
+fn attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=\"");
+    let mut rest = element;
+    while let Some(pos) = rest.find(&pattern) {
+        // Skip longer names ending the same way, e.g. `sodipodi:docname`
+        let preceded_by_space = rest[..pos]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        let value = &rest[pos + pattern.len()..];
+        if preceded_by_space {
+            return value.find('"').map(|end| &value[..end]);
+        }
+        rest = value;
+    }
+    None
+}
+
+/// Polygons of an SVG path made of straight segments (`M`, `L`, `H`, `V`,
+/// `Z`, absolute or relative).
+fn parse_path(data: &str) -> Result<Vec<Vec<Point>>, String> {
+    let mut rings: Vec<Vec<Point>> = Vec::new();
+    let mut ring: Vec<Point> = Vec::new();
+    let mut current = Point::default();
+    let mut start = Point::default();
+    let mut command = None;
+    let mut numbers: Vec<f32> = Vec::new();
+
+    let tokens = data
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty());
+    for token in tokens {
+        if let Ok(number) = token.parse::<f32>() {
+            numbers.push(number);
+        } else {
+            if let Some(cmd) = command {
+                apply(
+                    cmd,
+                    &numbers,
+                    &mut current,
+                    &mut start,
+                    &mut ring,
+                    &mut rings,
+                )?;
+            }
+            numbers.clear();
+            let mut chars = token.chars();
+            let cmd = chars.next().ok_or("empty token")?;
+            command = Some(cmd);
+            // Number glued to the command, such as `m10,20`
+            let rest = chars.as_str();
+            if !rest.is_empty() {
+                numbers.push(rest.parse().map_err(|_| format!("bad token {token}"))?);
+            }
+        }
+    }
+    if let Some(cmd) = command {
+        apply(
+            cmd,
+            &numbers,
+            &mut current,
+            &mut start,
+            &mut ring,
+            &mut rings,
+        )?;
+    }
+    if ring.len() > 2 {
+        rings.push(ring);
+    }
+    Ok(rings)
+}
+
+fn apply(
+    cmd: char,
+    numbers: &[f32],
+    current: &mut Point,
+    start: &mut Point,
+    ring: &mut Vec<Point>,
+    rings: &mut Vec<Vec<Point>>,
+) -> Result<(), String> {
+    let relative = cmd.is_ascii_lowercase();
+    let offset = |p: &Point| if relative { *p } else { Point::default() };
+    match cmd.to_ascii_uppercase() {
+        'M' | 'L' => {
+            if !numbers.len().is_multiple_of(2) {
+                return Err(format!("odd number of coordinates after {cmd}"));
+            }
+            for (i, pair) in numbers.chunks(2).enumerate() {
+                let base = offset(current);
+                *current = Point::new(base.x + pair[0], base.y + pair[1]);
+                // Pairs following a move are implicit line-tos
+                if i == 0 && cmd.eq_ignore_ascii_case(&'m') {
+                    if ring.len() > 2 {
+                        rings.push(std::mem::take(ring));
+                    }
+                    ring.clear();
+                    *start = *current;
+                }
+                ring.push(*current);
+            }
+        }
+        'H' => {
+            for x in numbers {
+                current.x = offset(current).x + x;
+                ring.push(*current);
+            }
+        }
+        'V' => {
+            for y in numbers {
+                current.y = offset(current).y + y;
+                ring.push(*current);
+            }
+        }
+        'Z' => {
+            if ring.len() > 2 {
+                rings.push(std::mem::take(ring));
+            }
+            ring.clear();
+            *current = *start;
+        }
+        _ => return Err(format!("unsupported path command {cmd}")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::oms_to_local;
+
+    fn map() -> ProvinceMap {
+        ProvinceMap::load("assets/belgium_map.svg").unwrap()
+    }
+
+    fn province_id(map: &ProvinceMap, long: f32, lat: f32) -> Option<&str> {
+        let (x, y) = oms_to_local(long, lat);
+        map.province_of(Point::new(x, y)).map(|p| p.id.as_str())
+    }
+
+    #[test]
+    fn test_parse_relative_path() {
+        let rings = parse_path("m 10,10 5,0 v 5 h -5 z m 20,0 l 1,0 0,1 z").unwrap();
+        assert_eq!(rings.len(), 2);
+        assert_eq!(rings[0][2], Point::new(15., 15.));
+        assert_eq!(rings[1][0], Point::new(30., 10.));
+    }
+
+    #[test]
+    fn test_load_provinces() {
+        let map = map();
+        assert_eq!(map.provinces.len(), 11);
+    }
+
+    #[test]
+    fn test_province_of_cities() {
+        let map = map();
+        // Soignies, Liège, Antwerp, Brussels, Arlon
+        assert_eq!(province_id(&map, 4.0686, 50.5792), Some("BE-WHT"));
+        assert_eq!(province_id(&map, 5.5736, 50.6451), Some("BE-WLG"));
+        assert_eq!(province_id(&map, 4.3997, 51.2211), Some("BE-VAN"));
+        assert_eq!(province_id(&map, 4.3517, 50.8467), Some("BE-BRU"));
+        assert_eq!(province_id(&map, 5.8167, 49.6833), Some("BE-WLX"));
+        // Paris
+        assert_eq!(province_id(&map, 2.3522, 48.8566), None);
+    }
+
+    #[test]
+    fn test_nearest_edge_point() {
+        let map = ProvinceMap {
+            size: Point::new(10., 10.),
+            provinces: vec![Province {
+                id: "BE-VAN".into(),
+                name: "Square".into(),
+                rings: vec![vec![
+                    Point::new(0., 0.),
+                    Point::new(4., 0.),
+                    Point::new(4., 4.),
+                    Point::new(0., 4.),
+                ]],
+            }],
+        };
+        assert_eq!(
+            map.nearest_edge_point(Point::new(6., 2.)),
+            Some(Point::new(4., 2.))
+        );
+        assert_eq!(
+            map.nearest_edge_point(Point::new(-1., 5.)),
+            Some(Point::new(0., 4.))
+        );
+    }
+
+    #[test]
+    fn test_outline() {
+        let map = map();
+        let outline = map.outline();
+        let edges: usize = map.provinces.iter().map(|p| p.edges().count()).sum();
+        assert!(!outline.is_empty() && outline.segments.len() < edges / 2);
+        // Brussels is surrounded by Flemish Brabant
+        let brussels = map.provinces.iter().find(|p| p.id == "BE-BRU").unwrap();
+        assert!(
+            brussels
+                .edges()
+                .all(|edge| !outline.segments.contains(&edge))
+        );
+
+        let (x, y) = oms_to_local(2.3522, 48.8566);
+        let paris = Point::new(x, y);
+        assert!(!map.in_country(paris));
+        let snapped = outline.nearest_point(paris).unwrap();
+        assert!(map.in_country(snapped));
+        assert!(snapped.distance(paris) > 50.);
+    }
+
+    #[test]
+    fn test_region_from_id() {
+        assert_eq!(Region::from_id("BE-VOV"), Some(Region::Flanders));
+        assert_eq!(Region::from_id("BE-WNA"), Some(Region::Wallonia));
+        assert_eq!(Region::from_id("BE-BRU"), Some(Region::Brussels));
+        assert_eq!(Region::from_id("svg1"), None);
+    }
+}
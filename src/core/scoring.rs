@@ -0,0 +1,78 @@
+/// Points given for a guess according to its distance to the answer,
+/// in map units.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoringCurve {
+    pub max: u32,
+    /// Distance under which the guess gets every point.
+    pub tolerance: f32,
+    /// Distance, past the tolerance, at which the guess gets no point.
+    pub range: f32,
+    /// Points added when the guess is in the province of the city, however
+    /// far from it.
+    pub province_bonus: u32,
+}
+
+impl Default for ScoringCurve {
+    fn default() -> Self {
+        Self {
+            max: 100,
+            tolerance: 5.,
+            range: 400.,
+            province_bonus: 20,
+        }
+    }
+}
+
+impl ScoringCurve {
+    pub fn points(&self, distance: f32) -> u32 {
+        let scaled = (distance - self.tolerance).clamp(0., self.range) / self.range;
+        self.max - (scaled * self.max as f32) as u32
+    }
+
+    /// Farthest distance still giving `points`.
+    pub fn distance_for(&self, points: u32) -> f32 {
+        let missed = self.max.saturating_sub(points) as f32 / self.max as f32;
+        self.tolerance + missed * self.range
+    }
+
+    /// Score bands, each quarter of the points, as (points, outer radius).
+    pub fn bands(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        (0..4)
+            .rev()
+            .map(move |quarter| quarter * self.max / 4)
+            .map(|points| (points, self.distance_for(points)))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+pub struct Score {
+    pub total: u32,
+    pub max: u32,
+    /// Guesses that got the [`ScoringCurve::province_bonus`].
+    pub province_bonuses: u32,
+    /// Hints taken over the round.
+    pub hints: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_bounds() {
+        let curve = ScoringCurve::default();
+        assert_eq!(curve.points(0.), 100);
+        assert_eq!(curve.points(5.), 100);
+        assert_eq!(curve.points(205.), 50);
+        assert_eq!(curve.points(10_000.), 0);
+    }
+
+    #[test]
+    fn test_bands_match_points() {
+        let curve = ScoringCurve::default();
+        for (points, radius) in curve.bands() {
+            assert_eq!(curve.points(radius), points);
+        }
+    }
+}
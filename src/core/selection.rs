@@ -0,0 +1,245 @@
+//! How the cities of a round are drawn, see [`SelectionStrategy`].
+
+use std::collections::{HashMap, HashSet};
+
+use rand::{Rng, rngs::StdRng, seq::IndexedRandom};
+
+use super::{
+    city::{CityId, CityRecord},
+    projection::distance_km,
+    regions::ProvinceMap,
+};
+
+/// Draws the cities of a round among the candidates left by the difficulty
+/// and the study set.
+pub trait SelectionStrategy: std::fmt::Debug + Send + Sync {
+    /// Up to `amount` distinct cities of `candidates`.
+    fn select(
+        &mut self,
+        candidates: &[&CityRecord],
+        amount: usize,
+        rng: &mut StdRng,
+    ) -> Vec<CityRecord>;
+
+    /// Called when `city` is asked. Cities drawn are not all asked, as
+    /// rounds are drawn again when the settings change.
+    fn asked(&mut self, _city: &CityRecord) {}
+}
+
+/// Candidates in a random order, important ones being more likely to come
+/// first: sorted by `u^(1/weight)` for a uniform `u`, so that any prefix is
+/// a weighted draw without replacement.
+fn weighted_order<'a>(candidates: &[&'a CityRecord], rng: &mut StdRng) -> Vec<&'a CityRecord> {
+    let mut keyed: Vec<(f64, &CityRecord)> = candidates
+        .iter()
+        // Logarithm of the key, which keeps its order without underflowing
+        .map(|&c| (rng.random::<f64>().ln() / c.importance(), c))
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keyed.into_iter().map(|(_, c)| c).collect()
+}
+
+/// Distinct cities, important ones being more likely.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Weighted;
+
+impl SelectionStrategy for Weighted {
+    fn select(
+        &mut self,
+        candidates: &[&CityRecord],
+        amount: usize,
+        rng: &mut StdRng,
+    ) -> Vec<CityRecord> {
+        candidates
+            .choose_multiple_weighted(rng, amount, |c| c.importance())
+            .map(|picked| picked.map(|c| (*c).clone()).collect())
+            // Only fails on invalid weights, which importance never gives
+            .unwrap_or_default()
+    }
+}
+
+/// Weighted draw keeping the cities of a round apart and, given the
+/// provinces, sharing the round evenly between them. The constraints are
+/// dropped when the candidates cannot meet them, rather than cutting the
+/// round short.
+#[derive(Debug, Clone, Default)]
+pub struct Spread {
+    min_distance_km: f32,
+    provinces: Option<ProvinceMap>,
+}
+
+impl Spread {
+    pub fn new(min_distance_km: f32) -> Self {
+        Self {
+            min_distance_km,
+            provinces: None,
+        }
+    }
+
+    /// At most an even share of the round per province.
+    pub fn with_provinces(mut self, provinces: ProvinceMap) -> Self {
+        self.provinces = Some(provinces);
+        self
+    }
+}
+
+impl SelectionStrategy for Spread {
+    fn select(
+        &mut self,
+        candidates: &[&CityRecord],
+        amount: usize,
+        rng: &mut StdRng,
+    ) -> Vec<CityRecord> {
+        let order = weighted_order(candidates, rng);
+        let share = self
+            .provinces
+            .as_ref()
+            .filter(|map| !map.provinces.is_empty())
+            .map(|map| amount.div_ceil(map.provinces.len()));
+        let mut per_province: HashMap<Option<&str>, usize> = HashMap::new();
+        let mut picked: Vec<&CityRecord> = Vec::new();
+        for &city in &order {
+            if picked.len() == amount {
+                break;
+            }
+            let far_enough = picked
+                .iter()
+                .all(|other| distance_km(other.loc, city.loc) >= self.min_distance_km);
+            if !far_enough {
+                continue;
+            }
+            if let (Some(share), Some(map)) = (share, &self.provinces) {
+                // Looked up only for the cities reached, the order being long
+                let province = map.province_of(city.loc).map(|p| p.id.as_str());
+                let count = per_province.entry(province).or_default();
+                if province.is_some() && *count >= share {
+                    continue;
+                }
+                *count += 1;
+            }
+            picked.push(city);
+        }
+        // Too few candidates far enough apart
+        for &city in &order {
+            if picked.len() == amount {
+                break;
+            }
+            if !picked.iter().any(|p| p.id == city.id) {
+                picked.push(city);
+            }
+        }
+        picked.into_iter().cloned().collect()
+    }
+}
+
+/// Cities are not asked again until every candidate has been, the draw
+/// among the others being left to `inner`.
+#[derive(Debug)]
+pub struct NoRepeats {
+    inner: Box<dyn SelectionStrategy>,
+    asked: HashSet<CityId>,
+}
+
+impl NoRepeats {
+    pub fn new(inner: impl SelectionStrategy + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+            asked: HashSet::new(),
+        }
+    }
+}
+
+impl SelectionStrategy for NoRepeats {
+    fn select(
+        &mut self,
+        candidates: &[&CityRecord],
+        amount: usize,
+        rng: &mut StdRng,
+    ) -> Vec<CityRecord> {
+        let fresh: Vec<&CityRecord> = candidates
+            .iter()
+            .filter(|c| !self.asked.contains(&c.id))
+            .copied()
+            .collect();
+        let mut picked = self.inner.select(&fresh, amount, rng);
+        if picked.len() < amount {
+            // Every candidate has been asked, start over with the others
+            self.asked.clear();
+            let others: Vec<&CityRecord> = candidates
+                .iter()
+                .filter(|c| !picked.iter().any(|p| p.id == c.id))
+                .copied()
+                .collect();
+            let more = self.inner.select(&others, amount - picked.len(), rng);
+            picked.extend(more);
+        }
+        picked
+    }
+
+    fn asked(&mut self, city: &CityRecord) {
+        self.asked.insert(city.id);
+        self.inner.asked(city);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::core::load_database;
+
+    fn cities() -> Vec<CityRecord> {
+        load_database("database/belgium_cities.json").unwrap()
+    }
+
+    #[test]
+    fn test_spread() {
+        let cities = cities();
+        let candidates: Vec<&CityRecord> = cities.iter().collect();
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let mut spread = Spread::new(20.).with_provinces(provinces.clone());
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..20 {
+            let round = spread.select(&candidates, 10, &mut rng);
+            assert_eq!(round.len(), 10);
+            for (i, a) in round.iter().enumerate() {
+                for b in &round[i + 1..] {
+                    assert!(distance_km(a.loc, b.loc) >= 20., "{a:?} {b:?}");
+                }
+            }
+            let mut seen = HashSet::new();
+            for city in &round {
+                let province = provinces.province_of(city.loc).map(|p| &p.id);
+                assert!(province.is_none() || seen.insert(province), "{province:?}");
+            }
+        }
+
+        // Constraints dropped rather than a shorter round
+        let close: Vec<&CityRecord> = candidates[..3].to_vec();
+        let mut strict = Spread::new(1000.);
+        assert_eq!(strict.select(&close, 3, &mut rng).len(), 3);
+    }
+
+    #[test]
+    fn test_no_repeats() {
+        let cities = cities();
+        let candidates: Vec<&CityRecord> = cities.iter().take(25).collect();
+        let mut strategy = NoRepeats::new(Weighted);
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut asked = HashSet::new();
+        for _ in 0..2 {
+            // Drawn but not asked, as when the settings change
+            strategy.select(&candidates, 10, &mut rng);
+            for city in strategy.select(&candidates, 10, &mut rng) {
+                assert!(asked.insert(city.id), "{} asked twice", city.names.default);
+                strategy.asked(&city);
+            }
+        }
+        // The 5 left come first, then the pool starts over
+        let third = strategy.select(&candidates, 10, &mut rng);
+        let ids: HashSet<_> = third.iter().map(|c| c.id).collect();
+        assert_eq!(ids.len(), 10);
+        assert_eq!(ids.difference(&asked).count(), 5);
+    }
+}
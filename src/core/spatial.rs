@@ -0,0 +1,191 @@
+//! Uniform grid over the cities, for nearest neighbour queries.
+
+use std::collections::HashMap;
+
+use super::projection::Point;
+
+/// Side of a grid cell in map units, about 7 km.
+pub const DEFAULT_CELL_SIZE: f32 = 20.;
+
+/// Positions bucketed by grid cell. Queries return indices into the slice
+/// the index was built from.
+#[derive(Debug, Clone, Default)]
+pub struct CityIndex {
+    cell_size: f32,
+    points: Vec<Point>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Smallest and largest occupied cells, bounding the searches.
+    min: (i32, i32),
+    max: (i32, i32),
+}
+
+impl CityIndex {
+    pub fn new(points: Vec<Point>) -> Self {
+        Self::with_cell_size(points, DEFAULT_CELL_SIZE)
+    }
+
+    pub fn with_cell_size(points: Vec<Point>, cell_size: f32) -> Self {
+        let mut index = Self {
+            cell_size,
+            points: Vec::new(),
+            cells: HashMap::new(),
+            min: (i32::MAX, i32::MAX),
+            max: (i32::MIN, i32::MIN),
+        };
+        for (i, &point) in points.iter().enumerate() {
+            let cell = index.cell_of(point);
+            index.cells.entry(cell).or_default().push(i);
+            index.min = (index.min.0.min(cell.0), index.min.1.min(cell.1));
+            index.max = (index.max.0.max(cell.0), index.max.1.max(cell.1));
+        }
+        index.points = points;
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    fn cell_of(&self, point: Point) -> (i32, i32) {
+        (
+            (point.x / self.cell_size).floor() as i32,
+            (point.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Indices in the cells at `ring` cells, in Chebyshev distance, from
+    /// `center`.
+    fn ring(&self, center: (i32, i32), ring: i32) -> impl Iterator<Item = usize> + '_ {
+        let (cx, cy) = center;
+        let border = (cx - ring..=cx + ring).flat_map(move |x| {
+            (cy - ring..=cy + ring)
+                .filter(move |&y| ring == 0 || (x - cx).abs() == ring || (y - cy).abs() == ring)
+                .map(move |y| (x, y))
+        });
+        border
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+
+    /// Rings to visit from `center` before every occupied cell is covered.
+    fn max_ring(&self, center: (i32, i32)) -> i32 {
+        [
+            center.0 - self.min.0,
+            self.max.0 - center.0,
+            center.1 - self.min.1,
+            self.max.1 - center.1,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+        .max(0)
+    }
+
+    /// Index of the point closest to `point`.
+    pub fn nearest(&self, point: Point) -> Option<usize> {
+        self.k_nearest(point, 1).first().copied()
+    }
+
+    /// Indices of the `k` points closest to `point`, closest first.
+    pub fn k_nearest(&self, point: Point, k: usize) -> Vec<usize> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let center = self.cell_of(point);
+        let mut found: Vec<(usize, f32)> = Vec::new();
+        for ring in 0..=self.max_ring(center) {
+            // Points of this ring and further are further than this
+            if found.len() >= k && found[k - 1].1 <= (ring - 1) as f32 * self.cell_size {
+                break;
+            }
+            found.extend(
+                self.ring(center, ring)
+                    .map(|i| (i, self.points[i].distance(point))),
+            );
+            found.sort_by(|a, b| a.1.total_cmp(&b.1));
+            found.truncate(k);
+        }
+        found.into_iter().map(|(i, _)| i).collect()
+    }
+
+    /// Indices of the points at most `radius` away from `point`, in no
+    /// particular order.
+    pub fn within(&self, point: Point, radius: f32) -> Vec<usize> {
+        let (min_x, min_y) = self.cell_of(Point::new(point.x - radius, point.y - radius));
+        let (max_x, max_y) = self.cell_of(Point::new(point.x + radius, point.y + radius));
+        let (min_x, min_y) = (min_x.max(self.min.0), min_y.max(self.min.1));
+        let (max_x, max_y) = (max_x.min(self.max.0), max_y.min(self.max.1));
+        (min_x..=max_x)
+            .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(|&i| self.points[i].distance(point) <= radius)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest() {
+        let points = vec![
+            Point::new(0., 0.),
+            Point::new(19., 0.),
+            Point::new(21., 0.),
+            Point::new(-150., 80.),
+        ];
+        let index = CityIndex::new(points);
+        assert_eq!(index.nearest(Point::new(1., 1.)), Some(0));
+        // Across a cell border
+        assert_eq!(index.nearest(Point::new(20.5, 0.)), Some(2));
+        assert_eq!(index.nearest(Point::new(19.5, 0.)), Some(1));
+        // Far from every point
+        assert_eq!(index.nearest(Point::new(-500., 500.)), Some(3));
+        assert_eq!(CityIndex::new(Vec::new()).nearest(Point::default()), None);
+    }
+
+    /// Pseudo-random points over the map, without pulling `rand` in.
+    fn scattered(count: usize) -> Vec<Point> {
+        let mut state = 12_345_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as f32 / u32::MAX as f32
+        };
+        (0..count)
+            .map(|_| Point::new(next() * 750. - 375., next() * 610. - 305.))
+            .collect()
+    }
+
+    #[test]
+    fn test_queries_match_linear_scan() {
+        let points = scattered(2000);
+        let index = CityIndex::new(points.clone());
+        for query in scattered(50).into_iter().chain([Point::new(900., -900.)]) {
+            let mut by_distance: Vec<usize> = (0..points.len()).collect();
+            by_distance.sort_by(|&a, &b| {
+                points[a]
+                    .distance(query)
+                    .total_cmp(&points[b].distance(query))
+            });
+            assert_eq!(index.k_nearest(query, 5), by_distance[..5]);
+
+            let mut within = index.within(query, 30.);
+            within.sort_unstable();
+            let mut expected: Vec<usize> = (0..points.len())
+                .filter(|&i| points[i].distance(query) <= 30.)
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(within, expected);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::{collections::HashSet, path::Path};
+
+use super::{
+    city::{CityId, CityRecord, fold_name},
+    error::GeoError,
+    files::json_file,
+};
+
+/// A named list of cities to practise, such as the ones of an exam.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StudySet {
+    pub name: String,
+    pub cities: Vec<CityId>,
+}
+
+impl StudySet {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, GeoError> {
+        let file =
+            std::fs::read_to_string(path).map_err(|e| GeoError::DataLoading(e.to_string()))?;
+        serde_json::from_str(&file).map_err(|e| GeoError::DataLoading(e.to_string()))
+    }
+
+    /// Every set saved in `dir`, sorted by name. No set if `dir` does not
+    /// exist.
+    pub fn load_all<P: AsRef<Path>>(dir: P) -> Result<Vec<Self>, GeoError> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(GeoError::DataLoading(e.to_string())),
+        };
+        let mut sets = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| GeoError::DataLoading(e.to_string()))?
+                .path();
+            if path.extension().is_some_and(|e| e == "json") {
+                sets.push(Self::load(path)?);
+            }
+        }
+        sets.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sets)
+    }
+
+    /// Saved as `<name>.json` in `dir`, refused if the name would leave it.
+    pub fn save<P: AsRef<Path>>(&self, dir: P) -> Result<(), GeoError> {
+        let dir = dir.as_ref();
+        let path = json_file(dir, &self.name)?;
+        std::fs::create_dir_all(dir).map_err(|e| GeoError::DataWriting(e.to_string()))?;
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| GeoError::DataWriting(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| GeoError::DataWriting(e.to_string()))
+    }
+
+    pub fn pool(&self) -> HashSet<CityId> {
+        self.cities.iter().copied().collect()
+    }
+}
+
+/// Names of a plain text list, one per line. For CSV lines only the first
+/// column is kept, in double quotes if it holds a separator. Empty lines,
+/// `#` comments and a `name` header are skipped.
+pub fn parse_study_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(first_field)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty() && !name.starts_with('#'))
+        .enumerate()
+        .filter(|(i, name)| !(*i == 0 && name.eq_ignore_ascii_case("name")))
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// First column of a CSV line. A quoted column ends at the closing quote,
+/// `""` standing for a quote in it.
+fn first_field(line: &str) -> String {
+    let line = line.trim();
+    let Some(quoted) = line.strip_prefix('"') else {
+        return line
+            .split([',', ';', '\t'])
+            .next()
+            .unwrap_or_default()
+            .into();
+    };
+    let mut field = String::new();
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '"' && chars.next_if_eq(&'"').is_none() {
+            break;
+        }
+        field.push(c);
+    }
+    field
+}
+
+/// Outcome of matching a list of names against the database.
+#[derive(Debug, Default)]
+pub struct StudyMatch {
+    pub matched: Vec<CityId>,
+    pub unmatched: Vec<String>,
+    /// Names matching several cities equally well, with the candidates.
+    pub ambiguous: Vec<(String, Vec<CityId>)>,
+}
+
+/// Find the city of each name, ignoring case, accents and punctuation, and
+/// allowing a few typos.
+pub fn match_study_list(names: &[String], cities: &[CityRecord]) -> StudyMatch {
+    let folded: Vec<Vec<String>> = cities
+        .iter()
+        .map(|city| city.names.all().map(fold_name).collect())
+        .collect();
+
+    let mut result = StudyMatch::default();
+    for name in names {
+        let wanted = fold_name(name);
+        let mut best = usize::MAX;
+        let mut candidates: Vec<CityId> = Vec::new();
+        for (city, names) in cities.iter().zip(&folded) {
+            let distance = names
+                .iter()
+                .map(|n| edit_distance(&wanted, n))
+                .min()
+                .unwrap_or(usize::MAX);
+            if distance < best {
+                best = distance;
+                candidates.clear();
+            }
+            if distance == best && !candidates.contains(&city.id) {
+                candidates.push(city.id);
+            }
+        }
+
+        if best > typo_tolerance(&wanted) {
+            result.unmatched.push(name.clone());
+        } else if let [id] = candidates[..] {
+            if !result.matched.contains(&id) {
+                result.matched.push(id);
+            }
+        } else {
+            result.ambiguous.push((name.clone(), candidates));
+        }
+    }
+    result
+}
+
+/// Typos allowed in a name: none in short names, where they would match
+/// another city, up to 2 in long ones.
+fn typo_tolerance(name: &str) -> usize {
+    (name.chars().count() / 5).min(2)
+}
+
+/// Levenshtein distance, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::parse_database;
+
+    fn cities() -> Vec<CityRecord> {
+        parse_database(
+            r#"[
+            {"name:default": "Liège", "name:nl": "Luik", "id": 1, "lat": 50.64, "lon": 5.57},
+            {"name:default": "Saint-Hubert", "id": 2, "lat": 50.03, "lon": 5.37},
+            {"name:default": "Heverlee", "id": 3, "lat": 50.86, "lon": 4.69},
+            {"name:default": "Oud-Heverlee", "id": 4, "lat": 50.84, "lon": 4.66},
+            {"name:default": "Hamme", "id": 5, "lat": 51.1, "lon": 4.13},
+            {"name:default": "Hamme", "id": 6, "lat": 50.78, "lon": 4.7}
+        ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_study_list() {
+        let list = "name;province\nLiège;Liège\n\n# Luxembourg\n\"Saint-Hubert\"\n";
+        assert_eq!(parse_study_list(list), ["Liège", "Saint-Hubert"]);
+
+        let quoted = "\"Braine-l'Alleud, Brabant\",Brabant wallon\n\"Le \"\"Roeulx\"\"\";x\n";
+        assert_eq!(
+            parse_study_list(quoted),
+            ["Braine-l'Alleud, Brabant", "Le \"Roeulx\""]
+        );
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir().join("geoquizz_study_test");
+        let set = StudySet {
+            name: "Liège v1.2".into(),
+            cities: Vec::new(),
+        };
+        set.save(&dir).unwrap();
+        assert_eq!(StudySet::load(dir.join("Liège v1.2.json")).unwrap(), set);
+
+        let outside = StudySet {
+            name: "../exam".into(),
+            cities: Vec::new(),
+        };
+        assert!(matches!(
+            outside.save(&dir),
+            Err(GeoError::InvalidFileName(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_match_study_list() {
+        let names: Vec<String> = ["LIEGE", "luik", "saint hubert", "Heverle", "Hamme", "Gent"]
+            .map(String::from)
+            .into();
+        let result = match_study_list(&names, &cities());
+        assert_eq!(result.matched, [CityId(1), CityId(2), CityId(3)]);
+        assert_eq!(result.unmatched, ["Gent"]);
+        assert_eq!(
+            result.ambiguous,
+            [("Hamme".to_string(), vec![CityId(5), CityId(6)])]
+        );
+    }
+
+    #[test]
+    fn test_fold_name() {
+        assert_eq!(
+            fold_name(" Écaussinnes-d'Enghien "),
+            "ecaussinnes d enghien"
+        );
+        assert_eq!(edit_distance("heverle", "heverlee"), 1);
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use super::{
+    city::{CityId, CityRecord},
+    error::GeoError,
+    projection::{distance_km, local_to_oms},
+    regions::ProvinceMap,
+};
+
+/// Cities closer than this are likely the same place twice.
+pub const NEAR_DUPLICATE_KM: f32 = 0.3;
+
+/// Problems found in a dataset. Errors make it unusable, warnings are worth
+/// a look but can be legitimate, such as two villages sharing a name.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<GeoError>,
+    pub warnings: Vec<GeoError>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Check the cities against each other and against the map. Checks needing
+/// the map are skipped if `provinces` is empty.
+pub fn validate_database(cities: &[CityRecord], provinces: &ProvinceMap) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    for (index, city) in cities.iter().enumerate() {
+        let names = [
+            ("name:default", Some(&city.names.default)),
+            ("name:fr", city.names.fr.as_ref()),
+            ("name:nl", city.names.nl.as_ref()),
+            ("name:de", city.names.de.as_ref()),
+            ("name:en", city.names.en.as_ref()),
+        ];
+        for (field, name) in names {
+            if name.is_some_and(|name| name.trim().is_empty()) {
+                report.errors.push(GeoError::EmptyName {
+                    index,
+                    name: city.names.default.clone(),
+                    field,
+                });
+            }
+        }
+    }
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (index, city) in cities.iter().enumerate() {
+        let key = city.names.default.trim().to_lowercase();
+        if let Some(&first) = seen.get(&key) {
+            report.warnings.push(GeoError::DuplicateName {
+                index,
+                name: city.names.default.clone(),
+                first,
+            });
+        } else {
+            seen.insert(key, index);
+        }
+    }
+
+    let mut ids: HashMap<CityId, usize> = HashMap::new();
+    for (index, city) in cities.iter().enumerate() {
+        if let Some(&first) = ids.get(&city.id) {
+            report.errors.push(GeoError::DuplicateId {
+                index,
+                name: city.names.default.clone(),
+                id: city.id.0,
+                first,
+            });
+        } else {
+            ids.insert(city.id, index);
+        }
+    }
+
+    report.warnings.extend(near_duplicates(cities));
+
+    if !provinces.provinces.is_empty() {
+        let (west, north) = local_to_oms(-provinces.size.x / 2., provinces.size.y / 2.);
+        let (east, south) = local_to_oms(provinces.size.x / 2., -provinces.size.y / 2.);
+        for (index, city) in cities.iter().enumerate() {
+            let name = city.names.default.clone();
+            if !(west..=east).contains(&city.lon) || !(south..=north).contains(&city.lat) {
+                report.errors.push(GeoError::OutOfBounds {
+                    index,
+                    name,
+                    lat: city.lat,
+                    lon: city.lon,
+                });
+            } else if !provinces.in_country(city.loc) {
+                report.errors.push(GeoError::OutsideCountry { index, name });
+            }
+        }
+    }
+
+    report
+}
+
+/// Pairs closer than [`NEAR_DUPLICATE_KM`], found by sweeping the cities
+/// sorted by latitude.
+fn near_duplicates(cities: &[CityRecord]) -> Vec<GeoError> {
+    // A degree of latitude is about 111 km
+    let max_lat_gap = NEAR_DUPLICATE_KM / 111.;
+    let mut order: Vec<usize> = (0..cities.len()).collect();
+    order.sort_by(|&a, &b| cities[a].lat.total_cmp(&cities[b].lat));
+
+    let mut found = Vec::new();
+    for (i, &a) in order.iter().enumerate() {
+        for &b in &order[i + 1..] {
+            if cities[b].lat - cities[a].lat > max_lat_gap {
+                break;
+            }
+            let km = distance_km(cities[a].loc, cities[b].loc);
+            if km < NEAR_DUPLICATE_KM {
+                let (index, other) = (a.max(b), a.min(b));
+                found.push(GeoError::NearDuplicate {
+                    index,
+                    name: cities[index].names.default.clone(),
+                    other,
+                    other_name: cities[other].names.default.clone(),
+                    km,
+                });
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CityNames, Point, load_database, oms_to_local};
+
+    fn city(name: &str, lat: f32, lon: f32) -> CityRecord {
+        let (x, y) = oms_to_local(lon, lat);
+        CityRecord {
+            id: CityId::synthetic(name, lat, lon),
+            names: CityNames {
+                default: name.to_string(),
+                fr: None,
+                nl: Some(String::new()),
+                de: None,
+                en: None,
+            },
+            lat,
+            lon,
+            loc: Point::new(x, y),
+            place: None,
+            population: None,
+            capital: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_database() {
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let cities = [
+            city("Ath", 50.63, 3.78),
+            city("ath", 50.631, 3.781),
+            // Lille, on the map but in France
+            city("Lille", 50.63, 3.06),
+            city("Paris", 48.86, 2.35),
+        ];
+        let report = validate_database(&cities, &provinces);
+
+        assert!(!report.is_valid());
+        let errors: Vec<String> = report.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(errors.len(), 6, "{errors:?}");
+        assert!(matches!(
+            report.errors[4],
+            GeoError::OutsideCountry { index: 2, .. }
+        ));
+        assert!(matches!(
+            report.errors[5],
+            GeoError::OutOfBounds { index: 3, .. }
+        ));
+        assert!(matches!(
+            report.warnings[..],
+            [
+                GeoError::DuplicateName {
+                    index: 1,
+                    first: 0,
+                    ..
+                },
+                GeoError::NearDuplicate {
+                    index: 1,
+                    other: 0,
+                    ..
+                }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_missing_name() {
+        let json = r#"[
+            {"name:default": "Ath", "lat": 50.63, "lon": 3.78},
+            {"lat": 50.45, "lon": 3.95}
+        ]"#;
+        let cities = crate::core::parse_database(json).unwrap();
+        let report = validate_database(&cities, &ProvinceMap::default());
+        assert!(matches!(
+            report.errors[..],
+            [GeoError::EmptyName {
+                index: 1,
+                field: "name:default",
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_dataset_is_valid() {
+        let cities = load_database("database/belgium_cities.json").unwrap();
+        let provinces = ProvinceMap::load("assets/belgium_map.svg").unwrap();
+        let report = validate_database(&cities, &provinces);
+        assert!(report.is_valid(), "{:?}", report.errors);
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::{Language, Quiz, local_to_oms},
+    types::{Exploring, GameState},
+};
+
+/// Latitude and longitude under the mouse, with the nearest city.
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_cursor_text)
+            .add_systems(Update, update_cursor_text);
+    }
+}
+
+#[derive(Component)]
+struct CursorText;
+
+fn setup_cursor_text(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.),
+            left: Val::Px(5.),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font_size: 14.,
+            ..default()
+        },
+        TextColor(Color::BLACK),
+        CursorText,
+    ));
+}
+
+/// The nearest city would give the answer away, it is only named while
+/// exploring or once the guess is made.
+fn update_cursor_text(
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    quiz: Res<Quiz>,
+    game_state: Res<GameState>,
+    exploring: Res<Exploring>,
+    language: Res<Language>,
+    mut text: Query<&mut Text, With<CursorText>>,
+) {
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera.single().ok())
+        .and_then(|(pos, (camera, transform))| camera.viewport_to_world_2d(transform, pos).ok());
+    let readout = cursor.map_or_else(String::new, |cursor| {
+        let (long, lat) = local_to_oms(cursor.x, cursor.y);
+        let mut readout = format!("{lat:.3}°N {long:.3}°E");
+        if (exploring.0 || *game_state != GameState::Guess)
+            && let Some(city) = quiz.guess_set().nearest_city(cursor.into())
+        {
+            readout.push_str(&format!(" — {}", city.name(*language)));
+        }
+        readout
+    });
+    for mut text in &mut text {
+        if **text != readout {
+            **text = readout.clone();
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::{CONTROL_POINTS, Quiz, local_to_oms, oms_to_local},
+    types::{City, DebugOverlay, GameState},
+};
+
+/// Weight of the last frame in the displayed frame rate.
+const FPS_SMOOTHING: f32 = 0.1;
+
+/// Debugging aids toggled with `F3`: every city, the province outlines, the
+/// calibration points, and a readout of the cursor, the frame rate and the
+/// game state.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>()
+            .add_systems(Startup, setup_debug_text)
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    show_city_to_find.run_if(resource_changed::<DebugOverlay>),
+                    (draw_debug_overlay, update_debug_text)
+                        .run_if(|overlay: Res<DebugOverlay>| overlay.0),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Component)]
+struct DebugText;
+
+fn setup_debug_text(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.),
+            right: Val::Px(5.),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font_size: 14.,
+            ..default()
+        },
+        TextColor(Color::BLACK),
+        Visibility::Hidden,
+        DebugText,
+    ));
+}
+
+fn toggle_debug_overlay(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut text: Query<&mut Visibility, With<DebugText>>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        overlay.0 = !overlay.0;
+    }
+    if overlay.is_changed() {
+        for mut vis in &mut text {
+            *vis = if overlay.0 {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// The city to find is shown with the overlay, and hidden again until the
+/// guess is made.
+fn show_city_to_find(
+    overlay: Res<DebugOverlay>,
+    game_state: Res<GameState>,
+    mut cities: Query<&mut Visibility, With<City>>,
+) {
+    for mut vis in &mut cities {
+        if overlay.0 {
+            *vis = Visibility::Visible;
+        } else if *game_state == GameState::Guess {
+            *vis = Visibility::Hidden;
+        }
+    }
+}
+
+fn draw_debug_overlay(mut gizmos: Gizmos, quiz: Res<Quiz>) {
+    for province in &quiz.provinces().provinces {
+        for ring in &province.rings {
+            // Rings are not closed by their last point
+            let points = ring.iter().chain(ring.first()).map(|p| Vec2::new(p.x, p.y));
+            gizmos.linestrip_2d(points, Color::srgb(0.2, 0.2, 0.8));
+        }
+    }
+    for city in quiz.guess_set().all_cities() {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(Vec2::new(city.loc.x, city.loc.y)),
+            1.,
+            Color::srgb(0.4, 0.4, 0.4),
+        );
+    }
+    // Where a control point is drawn, crossed, against where it is projected
+    for point in CONTROL_POINTS {
+        let map = Vec2::new(point.map.x, point.map.y);
+        gizmos.cross_2d(Isometry2d::from_translation(map), 4., Color::BLACK);
+        let (x, y) = oms_to_local(point.long, point.lat);
+        gizmos.circle_2d(
+            Isometry2d::from_translation(Vec2::new(x, y)),
+            3.,
+            Color::srgb(0.9, 0.1, 0.1),
+        );
+    }
+}
+
+fn update_debug_text(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    quiz: Res<Quiz>,
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    mut fps: Local<f32>,
+    mut text: Query<&mut Text, With<DebugText>>,
+) {
+    let delta = time.delta_secs();
+    if delta > 0. {
+        *fps += (1. / delta - *fps) * FPS_SMOOTHING;
+    }
+    let mut lines = vec![format!("{:.0} FPS", *fps), format!("{:?}", *game_state)];
+
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera.single().ok())
+        .and_then(|(pos, (camera, transform))| camera.viewport_to_world_2d(transform, pos).ok());
+    if let Some(cursor) = cursor {
+        let (long, lat) = local_to_oms(cursor.x, cursor.y);
+        lines.push(format!("x {:.1}, y {:.1}", cursor.x, cursor.y));
+        lines.push(format!("{lat:.4}°N, {long:.4}°E"));
+        if let Some(province) = quiz.provinces().province_of(cursor.into()) {
+            lines.push(province.name.clone());
+        }
+    }
+    for mut text in &mut text {
+        **text = lines.join("\n");
+    }
+}
@@ -0,0 +1,411 @@
+use bevy::{
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
+};
+
+use crate::{
+    camera::{CameraFlight, CameraTarget},
+    core::{CityRecord, Language, Quiz, fold_name},
+    hints::setup_hint_text,
+    types::{
+        CityAssets, CityId, CityNames, Exploring, GameConfig, GameMode, InfoColumn, Location,
+        MapLabel, WorldClickCatcher,
+    },
+};
+
+/// Distance from the cursor at which a dot is hovered, in pixels.
+const HOVER_RADIUS: f32 = 8.;
+/// Zoom used to centre the city found by the search.
+const SEARCH_SCALE: f32 = 0.3;
+/// Size of the found city's dot relative to the others.
+const HIGHLIGHT_SCALE: f32 = 3.;
+
+/// Every city shown as a dot, with a label on hover, a search box and the
+/// list of the cities of a clicked province.
+pub struct ExplorerPlugin;
+
+impl Plugin for ExplorerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Exploring>()
+            .init_resource::<Search>()
+            // Under the hints
+            .add_systems(Startup, setup_explorer_ui.after(setup_hint_text))
+            .add_systems(PostStartup, spawn_dots)
+            .add_systems(
+                Update,
+                (
+                    toggle_explorer,
+                    update_explorer_visibility.run_if(resource_changed::<Exploring>),
+                    (type_search, hover_label, list_province_cities)
+                        .run_if(|exploring: Res<Exploring>| exploring.0),
+                    update_search_text
+                        .run_if(resource_changed::<Search>.or(resource_changed::<Language>)),
+                    update_dot_labels.run_if(resource_changed::<Language>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Parent of the dots, hidden outside of the explorer.
+#[derive(Component)]
+struct ExplorerLayer;
+
+#[derive(Component)]
+struct ExplorerDot;
+
+/// Dot of the city found by the search.
+#[derive(Component)]
+struct Highlighted;
+
+/// Name next to a dot, placed by the label plugin.
+#[derive(Component)]
+struct DotLabel;
+
+#[derive(Component)]
+struct HoverLabel;
+
+/// Interface of the explorer, hidden outside of it.
+#[derive(Component)]
+struct ExplorerUi;
+
+#[derive(Component)]
+struct SearchText;
+
+#[derive(Component)]
+struct ProvinceList;
+
+/// Text typed in the search box.
+#[derive(Resource, Default)]
+struct Search(String);
+
+fn spawn_dots(
+    mut commands: Commands,
+    quiz: Res<Quiz>,
+    city_assets: Res<CityAssets>,
+    config: Res<GameConfig>,
+    language: Res<Language>,
+    mut exploring: ResMut<Exploring>,
+) {
+    exploring.0 = config.mode == GameMode::Explore;
+    commands
+        .spawn((
+            ExplorerLayer,
+            Transform::default(),
+            if exploring.0 {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            },
+        ))
+        .with_children(|layer| {
+            for record in quiz.guess_set().all_cities() {
+                let loc: Location = record.loc.into();
+                layer.spawn((
+                    ExplorerDot,
+                    record.id,
+                    record.names.clone(),
+                    Transform::from_translation(loc.0.extend(0.05)),
+                    loc,
+                    Mesh2d(city_assets.mesh.clone()),
+                    MeshMaterial2d(city_assets.material.clone()),
+                    children![(
+                        DotLabel,
+                        MapLabel {
+                            importance: record.importance() as f32,
+                        },
+                        Text2d::new(record.name(*language)),
+                        TextFont {
+                            font: city_assets.font.clone(),
+                            font_size: 12.,
+                            ..default()
+                        },
+                        TextColor(Color::BLACK),
+                        Transform::default(),
+                        Visibility::Inherited,
+                    )],
+                ));
+            }
+        });
+    commands.spawn((
+        HoverLabel,
+        Text2d::default(),
+        TextFont {
+            font: city_assets.font.clone(),
+            font_size: 15.,
+            ..default()
+        },
+        TextColor(Color::BLACK),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+}
+
+fn setup_explorer_ui(
+    mut commands: Commands,
+    language: Res<Language>,
+    column: Single<Entity, With<InfoColumn>>,
+) {
+    commands.spawn((
+        Node {
+            display: Display::None,
+            ..default()
+        },
+        Text::new(language.strings().search),
+        TextColor(Color::BLACK),
+        Visibility::Hidden,
+        ExplorerUi,
+        SearchText,
+        ChildOf(*column),
+    ));
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(30.),
+            right: Val::Px(5.),
+            width: Val::Percent(25.),
+            ..default()
+        },
+        Text::default(),
+        TextColor(Color::BLACK),
+        Visibility::Hidden,
+        ExplorerUi,
+        ProvinceList,
+    ));
+}
+
+fn toggle_explorer(keys: Res<ButtonInput<KeyCode>>, mut exploring: ResMut<Exploring>) {
+    if keys.just_pressed(KeyCode::Tab) {
+        exploring.0 = !exploring.0;
+        info!("Explorer {}", if exploring.0 { "on" } else { "off" });
+    }
+}
+
+fn update_explorer_visibility(
+    exploring: Res<Exploring>,
+    mut shown: Query<
+        (&mut Visibility, Option<&mut Node>),
+        Or<(With<ExplorerLayer>, With<ExplorerUi>)>,
+    >,
+    mut label: Query<
+        &mut Visibility,
+        (
+            With<HoverLabel>,
+            Without<ExplorerLayer>,
+            Without<ExplorerUi>,
+        ),
+    >,
+) {
+    let visibility = if exploring.0 {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for (mut vis, node) in &mut shown {
+        *vis = visibility;
+        // Not taking room among the other texts either
+        if let Some(mut node) = node {
+            node.display = if exploring.0 {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+    // Shown again by `hover_label` when needed
+    if !exploring.0 {
+        for mut vis in &mut label {
+            *vis = Visibility::Hidden;
+        }
+    }
+}
+
+/// Letters typed while exploring go to the search box, `Enter` centres the
+/// city found.
+fn type_search(
+    mut commands: Commands,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut search: ResMut<Search>,
+    quiz: Res<Quiz>,
+    mut dots: Query<(Entity, &CityId, &mut Transform, Has<Highlighted>), With<ExplorerDot>>,
+    flight: Option<ResMut<CameraFlight>>,
+) {
+    let mut center = false;
+    for ev in keyboard.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+        match ev.key_code {
+            KeyCode::Backspace => {
+                search.0.pop();
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter => center = true,
+            _ => {
+                if let Some(text) = &ev.text {
+                    search.0.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+    if !search.is_changed() && !center {
+        return;
+    }
+
+    let found = find_city(&search.0, quiz.guess_set().all_cities());
+    for (entity, id, mut transform, highlighted) in &mut dots {
+        let is_found = found.is_some_and(|city| city.id == *id);
+        if is_found && !highlighted {
+            commands.entity(entity).insert(Highlighted);
+            transform.scale = Vec3::splat(HIGHLIGHT_SCALE);
+        } else if !is_found && highlighted {
+            commands.entity(entity).remove::<Highlighted>();
+            transform.scale = Vec3::ONE;
+        }
+    }
+    if center
+        && let Some(city) = found
+        && let Some(mut flight) = flight
+    {
+        flight.0 = Some(CameraTarget {
+            center: Vec2::new(city.loc.x, city.loc.y),
+            scale: SEARCH_SCALE,
+        });
+    }
+}
+
+/// Best match of `query`: a name equal to it, else starting with it, else
+/// containing it, ignoring case and accents.
+fn find_city<'a>(query: &str, cities: &'a [CityRecord]) -> Option<&'a CityRecord> {
+    let query = fold_name(query);
+    if query.is_empty() {
+        return None;
+    }
+    cities
+        .iter()
+        .filter_map(|city| {
+            let rank = city
+                .names
+                .all()
+                .map(fold_name)
+                .filter_map(|name| {
+                    if name == query {
+                        Some(0)
+                    } else if name.starts_with(&query) {
+                        Some(1)
+                    } else if name.contains(&query) {
+                        Some(2)
+                    } else {
+                        None
+                    }
+                })
+                .min()?;
+            Some((rank, city))
+        })
+        // Bigger cities first among equal matches
+        .min_by(|(a, x), (b, y)| a.cmp(b).then(y.importance().total_cmp(&x.importance())))
+        .map(|(_, city)| city)
+}
+
+/// Label of the dot under the cursor, or else of the city found.
+fn hover_label(
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform, &Projection)>,
+    dots: Query<(&Location, &CityNames, Has<Highlighted>), With<ExplorerDot>>,
+    language: Res<Language>,
+    mut label: Query<(&mut Text2d, &mut Transform, &mut Visibility), With<HoverLabel>>,
+) {
+    let Ok((mut text, mut transform, mut vis)) = label.single_mut() else {
+        return;
+    };
+    let hovered = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera.single().ok())
+        .and_then(|(cursor, (camera, camera_transform, projection))| {
+            let cursor = camera.viewport_to_world_2d(camera_transform, cursor).ok()?;
+            let scale = match projection {
+                Projection::Orthographic(projection) => projection.scale,
+                _ => 1.,
+            };
+            dots.iter()
+                .map(|(loc, names, _)| (loc.0.distance(cursor), loc, names))
+                .filter(|(distance, _, _)| *distance < HOVER_RADIUS * scale)
+                .min_by(|(a, _, _), (b, _, _)| a.total_cmp(b))
+                .map(|(_, loc, names)| (loc, names))
+        });
+    let shown = hovered.or_else(|| {
+        dots.iter()
+            .find(|(_, _, highlighted)| *highlighted)
+            .map(|(loc, names, _)| (loc, names))
+    });
+
+    match shown {
+        Some((loc, names)) => {
+            **text = names.get(*language).to_string();
+            transform.translation = (loc.0 + Vec2::new(0., 10.)).extend(0.4);
+            *vis = Visibility::Visible;
+        }
+        None => *vis = Visibility::Hidden,
+    }
+}
+
+/// Clicking a province lists its cities.
+fn list_province_cities(
+    clicks: Query<&Interaction, (Changed<Interaction>, With<WorldClickCatcher>)>,
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    quiz: Res<Quiz>,
+    language: Res<Language>,
+    mut list: Query<&mut Text, With<ProvinceList>>,
+) {
+    if !clicks.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.single(), camera.single()) else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|pos| camera.viewport_to_world_2d(camera_transform, pos).ok())
+    else {
+        return;
+    };
+    let provinces = quiz.provinces();
+    let Some(province) = provinces.province_of(cursor.into()) else {
+        return;
+    };
+    let mut names: Vec<&str> = quiz
+        .guess_set()
+        .all_cities()
+        .iter()
+        .filter(|city| province.contains(city.loc))
+        .map(|city| city.name(*language))
+        .collect();
+    names.sort_unstable();
+    for mut text in &mut list {
+        **text = format!("{} ({})\n{}", province.name, names.len(), names.join(", "));
+    }
+}
+
+fn update_search_text(
+    search: Res<Search>,
+    language: Res<Language>,
+    mut text: Query<&mut Text, With<SearchText>>,
+) {
+    for mut text in &mut text {
+        **text = format!("{}{}", language.strings().search, search.0);
+    }
+}
+
+fn update_dot_labels(
+    language: Res<Language>,
+    dots: Query<&CityNames, With<ExplorerDot>>,
+    mut labels: Query<(&ChildOf, &mut Text2d), With<DotLabel>>,
+) {
+    for (child_of, mut text) in &mut labels {
+        if let Ok(names) = dots.get(child_of.parent()) {
+            **text = names.get(*language).to_string();
+        }
+    }
+}
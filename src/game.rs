@@ -1,25 +1,41 @@
-use bevy::{camera::prelude::Visibility, color::palettes::basic::GREEN, input::keyboard::KeyboardInput, prelude::*};
-use bevy_svg::prelude::Origin;
-use std::path::PathBuf;
+use bevy::{camera::prelude::Visibility, color::palettes::basic::GREEN, prelude::*};
 
 use crate::{
     assets::{DEFAULT_BG, DEFAULT_BORDER},
+    controls::{ControlsPlugin, keyboard_controls},
+    core::{Answer, Difficulty, History, Language, ProvinceMap, Quiz, StudySet, UiStrings},
+    cursor::CursorPlugin,
+    explorer::ExplorerPlugin,
+    hints::HintPlugin,
+    labels::LabelPlugin,
+    region_quiz::RegionQuizPlugin,
+    study::StudyPlugin,
     types::{
-        City, CityAssets, CityNameToGuess, GameState, GuessAssets, GuessSet, GuessType, Location,
-        Score, ScoreText, SpawnCity, ValidatedGuess, WorldClickCatcher,
+        ActionButton, BundleCity, City, CityAssets, CityLabel, CityNameToGuess, CityNames,
+        ConfirmLabel, ConfirmPressed, Dataset, DebugOverlay, Exploring, GameConfig, GameMode,
+        GameState, GuessAssets, GuessEvaluated, GuessType, HintPressed, InfoColumn, MapLabel,
+        MapSize, OutsideCountry, OutsideGuesses, OutsideWarning, Paused, Profile, RegionPicked,
+        Score, ScoreText, SpawnCity, StudySets, UiLabel, ValidatedGuess, WorldClickCatcher,
     },
 };
 
-const MAX_POINTS: u32 = 100;
-
+/// Game resources and round initialisation, independent of the rendering.
 pub struct InitGamePlugin;
 
 impl Plugin for InitGamePlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<SpawnCity>()
+            .add_message::<HintPressed>()
+            .add_message::<RegionPicked>()
             .add_message::<ValidatedGuess>()
+            .add_message::<ConfirmPressed>()
+            .add_message::<GuessEvaluated>()
             .insert_resource(GameState::Guess)
             .init_resource::<Score>()
+            .init_resource::<Language>()
+            .init_resource::<GameConfig>()
+            .init_resource::<Profile>()
+            .init_resource::<DebugOverlay>()
             .add_systems(
                 Startup,
                 (init_guess, trigger_spawn_city.after(init_guess)).chain(),
@@ -27,28 +43,154 @@ impl Plugin for InitGamePlugin {
     }
 }
 
+fn setup_info_column(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(30.),
+            left: Val::Px(5.),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(5.),
+            ..default()
+        },
+        InfoColumn,
+    ));
+}
+
 fn trigger_spawn_city(mut ev: MessageWriter<SpawnCity>) {
     ev.write(SpawnCity);
 }
 
-fn init_guess(mut commands: Commands) {
-    commands.init_resource::<GuessSet>();
+fn init_guess(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    profile: Res<Profile>,
+    mut dataset: ResMut<Dataset>,
+) {
+    // Moved into the quiz
+    let cities = std::mem::take(&mut dataset.0);
+    commands.remove_resource::<Dataset>();
+    // Only needed by province answers, the game can go on without
+    let provinces = ProvinceMap::load(config.map_file()).unwrap_or_else(|e| {
+        warn!("No provinces: {e}");
+        ProvinceMap::default()
+    });
+    commands.insert_resource(MapSize(Vec2::new(provinces.size.x, provinces.size.y)));
+    let selection = config.mode.selection(&provinces);
+    let mut quiz = Quiz::new(cities)
+        .with_provinces(provinces)
+        .with_selection(selection)
+        .with_round_length(config.round_length)
+        .with_difficulty(config.difficulty)
+        .with_german_speaking(config.mode == GameMode::RegionsGerman);
+    if let Some(seed) = config.seed {
+        quiz = quiz.with_seed(seed);
+    }
+    // An empty round would never start
+    if quiz.remaining() == 0 {
+        warn!("No {} city, asking all of them", config.difficulty.name());
+        quiz = quiz.with_difficulty(Difficulty::All);
+    }
+
+    let sets = config
+        .study_dir()
+        .map(|dir| {
+            StudySet::load_all(dir).unwrap_or_else(|e| {
+                warn!("Study sets not loaded: {e}");
+                Vec::new()
+            })
+        })
+        .unwrap_or_default();
+    let mut active = config.study_set.as_ref().and_then(|name| {
+        let found = sets.iter().position(|set| &set.name == name);
+        if found.is_none() {
+            warn!("No study set named {name}");
+        }
+        found
+    });
+    if let Some(index) = active
+        && let Err(e) = quiz.set_pool(Some(sets[index].pool()))
+    {
+        warn!("Not studying {}: {e}", sets[index].name);
+        quiz.set_pool(None).ok();
+        active = None;
+    }
+    commands.insert_resource(StudySets { sets, active });
+    info!(
+        "Playing {} as {}, {} {} cities per round",
+        config.mode.name(),
+        profile.0,
+        config.round_length,
+        config.difficulty.name()
+    );
+    commands.insert_resource(quiz);
+
+    let history = config
+        .history_file(&profile)
+        .map(|path| {
+            History::load(path).unwrap_or_else(|e| {
+                warn!("History not loaded: {e}");
+                History::default()
+            })
+        })
+        .unwrap_or_default();
+    commands.insert_resource(history);
 }
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.add_plugins((
+            ControlsPlugin,
+            CursorPlugin,
+            ExplorerPlugin,
+            HintPlugin,
+            LabelPlugin,
+            RegionQuizPlugin,
+            StudyPlugin,
+        ))
+        // Before the plugins fill it
+        .add_systems(PreStartup, setup_info_column)
+        .add_systems(
             Update,
             (click_to_spawn_circle, update_button, update_score_text),
         )
+        .add_systems(
+            Update,
+            (
+                cycle_language,
+                update_language.run_if(resource_changed::<Language>),
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            update_confirm_label.run_if(resource_changed::<GameState>),
+        )
+        .add_systems(
+            Update,
+            (
+                check_guess_location
+                    .after(click_to_spawn_circle)
+                    .after(keyboard_controls),
+                show_guess_validity,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            advance_game
+                .run_if(on_message::<ConfirmPressed>)
+                .after(update_button)
+                .after(check_guess_location),
+        )
         .add_systems(
             Update,
             (
                 despawn_city
                     .run_if(on_message::<SpawnCity>)
-                    .after(update_button)
+                    .after(advance_game)
                     .chain(),
                 evaluate_guess.run_if(on_message::<ValidatedGuess>),
                 spawn_city
@@ -64,6 +206,7 @@ impl Plugin for GamePlugin {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn click_to_spawn_circle(
     mut commands: Commands,
     i: Query<&Interaction, (Changed<Interaction>, With<WorldClickCatcher>)>,
@@ -71,84 +214,191 @@ fn click_to_spawn_circle(
     camera_q: Query<(&Camera, &GlobalTransform)>,
     guess_assets: Res<GuessAssets>,
     mut existing_circle: Query<(Entity, &mut Transform), With<GuessType>>,
+    paused: Res<Paused>,
+    exploring: Res<Exploring>,
+    config: Res<GameConfig>,
 ) {
-    let window = windows.single().unwrap();
+    if paused.0 || exploring.0 || config.mode.is_region_quiz() {
+        return;
+    }
+    // No window nor camera when running headless
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.single(), camera_q.single()) else {
+        return;
+    };
     if let Ok(interaction) = i.single()
         && *interaction == Interaction::Pressed
         && let Some(cursor_pos) = window.cursor_position()
+        && let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos)
     {
-        let (camera, camera_transform) = camera_q.single().unwrap();
-        let world_pos = camera
-            .viewport_to_world_2d(camera_transform, cursor_pos)
-            .unwrap();
-        if let Ok((entity, mut transform)) = existing_circle.single_mut() {
-            // Move the existing circle
-            transform.translation = world_pos.extend(0.1);
-            commands
-                .entity(entity)
-                .insert(GuessType::Location(world_pos));
-        } else {
-            commands.spawn((
-                Mesh2d(guess_assets.mesh.clone()),
-                MeshMaterial2d(guess_assets.material.clone()),
-                Transform::from_translation(world_pos.extend(0.1)),
-                GuessType::Location(world_pos),
-            ));
-        }
+        place_guess(
+            &mut commands,
+            &guess_assets,
+            existing_circle.single_mut().ok(),
+            world_pos,
+        );
     };
 }
 
-fn evaluate_guess(
-    guess_query: Query<&GuessType>,
-    anwser_query: Query<(&Name, &Location), With<City>>,
-    mut reveal_query: Query<&mut Visibility, With<City>>,
-    mut score: ResMut<Score>,
+/// Move the guess marker to `world_pos`, spawning it if needed.
+pub(crate) fn place_guess(
+    commands: &mut Commands,
+    guess_assets: &GuessAssets,
+    existing: Option<(Entity, Mut<Transform>)>,
+    world_pos: Vec2,
 ) {
-    if let Ok((_, loc_field)) = anwser_query.single() {
-        let loc = loc_field.0;
-        if let Ok(guess) = guess_query.single() {
-            match guess {
-                GuessType::Location(guess_pos) => {
-                    let distance = guess_pos.distance(loc) as u32;
-                    let points = calculate_score(distance, MAX_POINTS);
-                    info!("Distance: {distance}, given points: {points}");
-                    score.total += points;
-                    score.max += MAX_POINTS;
+    if let Some((entity, mut transform)) = existing {
+        // Move the existing circle
+        transform.translation = world_pos.extend(0.1);
+        commands
+            .entity(entity)
+            .insert(GuessType::Location(world_pos));
+    } else {
+        commands.spawn((
+            Mesh2d(guess_assets.mesh.clone()),
+            MeshMaterial2d(guess_assets.material.clone()),
+            Transform::from_translation(world_pos.extend(0.1)),
+            GuessType::Location(world_pos),
+        ));
+    }
+}
 
-                    for mut vis in reveal_query.iter_mut() {
-                        *vis = Visibility::Visible;
-                    }
+/// Snap guesses outside of the country to the border, or flag them, as
+/// configured.
+fn check_guess_location(
+    mut commands: Commands,
+    mut guesses: Query<
+        (Entity, &mut GuessType, &mut Transform, Has<OutsideCountry>),
+        Changed<GuessType>,
+    >,
+    quiz: Res<Quiz>,
+    config: Res<GameConfig>,
+) {
+    for (entity, mut guess, mut transform, flagged) in &mut guesses {
+        let GuessType::Location(pos) = *guess else {
+            continue;
+        };
+        let inside = quiz.accepts_location(pos.into());
+        match config.outside_guesses {
+            OutsideGuesses::Snap if !inside => {
+                if let Some(border) = quiz.outline().nearest_point(pos.into()) {
+                    let border = Vec2::new(border.x, border.y);
+                    info!("Guess snapped to the border at {border}");
+                    *guess = GuessType::Location(border);
+                    transform.translation = border.extend(transform.translation.z);
                 }
-                GuessType::Name(_) => todo!(),
             }
-        } else {
-            info!("No guess has been made yet.");
+            OutsideGuesses::Flag if !inside && !flagged => {
+                commands.entity(entity).insert(OutsideCountry);
+            }
+            _ if inside && flagged => {
+                commands.entity(entity).remove::<OutsideCountry>();
+            }
+            _ => {}
         }
     }
 }
 
-fn calculate_score(distance: u32, max: u32) -> u32 {
-    let scaled = distance.saturating_sub(5).clamp(0, 400) / 4;
-    max.saturating_sub(scaled)
+/// Dim the confirm button until there is a guess to confirm, and warn about
+/// guesses outside of the country.
+fn show_guess_validity(
+    game_state: Res<GameState>,
+    valid: Query<(), (With<GuessType>, Without<OutsideCountry>)>,
+    outside: Query<(), With<OutsideCountry>>,
+    mut confirm: Query<&mut TextColor, With<ConfirmLabel>>,
+    mut warning: Query<&mut Visibility, With<OutsideWarning>>,
+) {
+    let enabled = *game_state != GameState::Guess || !valid.is_empty();
+    for mut color in &mut confirm {
+        let target = TextColor(if enabled {
+            Color::srgb(0.9, 0.9, 0.9)
+        } else {
+            Color::srgb(0.45, 0.45, 0.45)
+        });
+        // Avoid flagging the text as changed every frame
+        color.set_if_neq(target);
+    }
+    let shown = !outside.is_empty() && *game_state == GameState::Guess;
+    for mut vis in &mut warning {
+        vis.set_if_neq(if shown {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn evaluate_guess(
+    guess_query: Query<&GuessType>,
+    mut reveal_query: Query<&mut Visibility, With<City>>,
+    mut quiz: ResMut<Quiz>,
+    mut score: ResMut<Score>,
+    mut history: ResMut<History>,
+    config: Res<GameConfig>,
+    profile: Res<Profile>,
+    mut evaluated: MessageWriter<GuessEvaluated>,
+) {
+    let Ok(guess) = guess_query.single() else {
+        info!("No guess has been made yet.");
+        return;
+    };
+    let answer = match guess {
+        GuessType::Location(pos) => Answer::Location((*pos).into()),
+        GuessType::Name(name) => Answer::Name(name.clone()),
+        GuessType::Region(region) => Answer::Region(*region),
+    };
+    let Ok(result) = quiz.submit(&answer) else {
+        return;
+    };
+    *score = quiz.score();
+    info!("Given points: {}", result.points);
+    history.record(&result);
+    if let Some(path) = config.history_file(&profile)
+        && let Err(e) = history.save(path)
+    {
+        warn!("History not saved: {e}");
+    }
+    if let Some(distance) = result.distance
+        && let GuessType::Location(pos) = guess
+    {
+        info!("Distance: {:.1} km", distance.km);
+        let (nearest, province) = result.surroundings.map_or((None, None), |s| {
+            (s.nearest.map(|city| city.names), s.province)
+        });
+        evaluated.write(GuessEvaluated {
+            city: result.city.id,
+            guess: *pos,
+            answer: Vec2::new(result.city.loc.x, result.city.loc.y),
+            distance_km: distance.km,
+            points: result.points,
+            nearest,
+            province,
+            province_bonus: result.province_bonus,
+        });
+    }
+
+    for mut vis in reveal_query.iter_mut() {
+        *vis = Visibility::Visible;
+    }
 }
 
 fn spawn_city(
     mut commands: Commands,
-    mut guess_set: ResMut<GuessSet>,
-    asset_server: Res<AssetServer>,
+    mut quiz: ResMut<Quiz>,
     city_assets: Res<CityAssets>,
+    language: Res<Language>,
+    debug_overlay: Res<DebugOverlay>,
 ) {
-    if let Some(city) = guess_set.to_guess.pop() {
-        let path: PathBuf = ["fonts", "FiraMono-Medium.ttf"].iter().collect();
-        let font: Handle<Font> = asset_server.load(path);
+    if let Some(record) = quiz.next_question() {
+        let city = BundleCity::new(record, *language);
         let location = city.loc.0;
-        let name = city.name.as_str();
+        let name = city.name.to_string();
         info!("spawning {}", name);
 
         commands.spawn((
-            city.clone(),
+            city,
             Transform::from_translation(location.extend(0.1)),
-            if cfg!(feature = "debug") {
+            if debug_overlay.0 {
                 Visibility::Visible
             } else {
                 Visibility::Hidden
@@ -161,18 +411,17 @@ fn spawn_city(
                 children![(
                     Text2d::from(name),
                     TextFont {
-                        font,
+                        font: city_assets.font.clone(),
                         font_size: 17.5,
                         ..Default::default()
                     },
                     Visibility::Inherited,
-                    // Node {..Default::default()},
-                    Transform::from_translation(Vec3 {
-                        x: 15.,
-                        y: 15.,
-                        z: 0.1
-                    }),
-                    Origin::Center,
+                    CityLabel,
+                    // The city asked always gets its label
+                    MapLabel {
+                        importance: f32::INFINITY,
+                    },
+                    Transform::from_translation(Vec3::Z * 0.1),
                 )]
             )],
         ));
@@ -214,9 +463,9 @@ fn update_button(
     interaction_query: Query<
         (
             &Interaction,
+            &ActionButton,
             &mut BackgroundColor,
             &mut BorderColor,
-            &Children,
         ),
         (
             Changed<Interaction>,
@@ -224,25 +473,21 @@ fn update_button(
             Without<WorldClickCatcher>,
         ),
     >,
-    keyboard: MessageReader<KeyboardInput>,
-    mut text_query: Query<&mut Text>,
-    mut guess_event: MessageWriter<ValidatedGuess>,
-    mut spawn_event: MessageWriter<SpawnCity>,
-    mut game_state: ResMut<GameState>,
+    mut confirm_event: MessageWriter<ConfirmPressed>,
+    mut hint_event: MessageWriter<HintPressed>,
 ) {
-    for (interaction, mut bg_color, mut border_color, children) in interaction_query {
-        let mut text = text_query.get_mut(children[0]).unwrap();
+    for (interaction, action, mut bg_color, mut border_color) in interaction_query {
         match *interaction {
             Interaction::Pressed => {
                 *border_color = BorderColor::all(Color::srgb(0.12, 0.4, 0.));
                 *bg_color = BackgroundColor(Color::from(GREEN));
-                if *game_state == GameState::Guess {
-                    **text = "Continue".to_string();
-                    *game_state = GameState::Standby;
-                    guess_event.write(ValidatedGuess);
-                } else {
-                    **text = "Confirm".to_string();
-                    spawn_event.write(SpawnCity);
+                match action {
+                    ActionButton::Confirm => {
+                        confirm_event.write(ConfirmPressed);
+                    }
+                    ActionButton::Hint => {
+                        hint_event.write(HintPressed);
+                    }
                 }
             }
             Interaction::Hovered => {
@@ -252,14 +497,126 @@ fn update_button(
             Interaction::None => {
                 *border_color = BorderColor::all(DEFAULT_BORDER);
                 *bg_color = BackgroundColor(DEFAULT_BG);
-                if *game_state == GameState::Guess {
-                    **text = "Confirm".to_string();
-                } else {
-                    **text = "Continue".to_string();
-                }
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn advance_game(
+    mut confirm_event: MessageReader<ConfirmPressed>,
+    mut guess_event: MessageWriter<ValidatedGuess>,
+    mut spawn_event: MessageWriter<SpawnCity>,
+    mut game_state: ResMut<GameState>,
+    mut quiz: ResMut<Quiz>,
+    mut score: ResMut<Score>,
+    paused: Res<Paused>,
+    exploring: Res<Exploring>,
+    valid_guesses: Query<(), (With<GuessType>, Without<OutsideCountry>)>,
+) {
+    // Several presses in the same frame count as one
+    confirm_event.clear();
+    if paused.0 || exploring.0 {
+        return;
+    }
+    match *game_state {
+        GameState::Guess if valid_guesses.is_empty() => {
+            info!("No guess to confirm yet.");
+        }
+        GameState::Guess => {
+            *game_state = GameState::Standby;
+            guess_event.write(ValidatedGuess);
+        }
+        GameState::Standby if quiz.remaining() == 0 => {
+            info!("Round over, score {}/{}", score.total, score.max);
+            *game_state = GameState::RoundOver;
+        }
+        GameState::Standby => {
+            spawn_event.write(SpawnCity);
+        }
+        GameState::RoundOver => {
+            if let Err(e) = quiz.start_round() {
+                warn!("{e}");
+            }
+            *score = quiz.score();
+            spawn_event.write(SpawnCity);
+        }
+    }
+}
+
+fn update_confirm_label(
+    game_state: Res<GameState>,
+    language: Res<Language>,
+    mut text: Query<&mut Text, With<ConfirmLabel>>,
+) {
+    for mut text in &mut text {
+        **text = button_label(&game_state, language.strings()).to_string();
+    }
+}
+
+fn button_label(game_state: &GameState, strings: &UiStrings) -> &'static str {
+    match game_state {
+        GameState::Guess => strings.confirm,
+        GameState::Standby => strings.next,
+        GameState::RoundOver => strings.new_round,
+    }
+}
+
+fn cycle_language(
+    keys: Res<ButtonInput<KeyCode>>,
+    exploring: Res<Exploring>,
+    mut language: ResMut<Language>,
+) {
+    // `L` is typed in the explorer search
+    if keys.just_pressed(KeyCode::KeyL) && !exploring.0 {
+        *language = language.next();
+        info!("Language set to {:?}", *language);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_language(
+    language: Res<Language>,
+    game_state: Res<GameState>,
+    quiz: Res<Quiz>,
+    mut labels: Query<(&mut Text, &UiLabel), Without<ConfirmLabel>>,
+    mut confirm: Query<&mut Text, With<ConfirmLabel>>,
+    mut cities: Query<(&mut Name, &CityNames), With<City>>,
+    mut city_labels: Query<(Entity, &mut Text2d), With<CityLabel>>,
+    parents: Query<&ChildOf>,
+    mut to_guess: Query<&mut TextSpan, With<CityNameToGuess>>,
+) {
+    let strings = language.strings();
+    for (mut text, label) in &mut labels {
+        **text = match label {
+            UiLabel::Find => strings.find,
+            UiLabel::Score => strings.score,
+            UiLabel::Paused => strings.paused,
+            UiLabel::Hint => strings.hint,
+            UiLabel::OutsideCountry => strings.outside_country,
+        }
+        .to_string();
+    }
+    for mut text in &mut confirm {
+        **text = button_label(&game_state, strings).to_string();
+    }
+    for (mut name, names) in &mut cities {
+        name.set(names.get(*language).to_string());
+    }
+    // Labels are under the dot of their city
+    for (entity, mut label) in &mut city_labels {
+        let names = parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| cities.get(ancestor).ok());
+        if let Some((_, names)) = names {
+            **label = names.get(*language).to_string();
+        }
+    }
+    if let Some(city) = quiz.current() {
+        for mut span in &mut to_guess {
+            **span = city.name(*language).to_string();
+        }
+    }
+}
+
 // fn switch_button_state() {}
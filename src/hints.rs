@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::{Hint, Language, Quiz},
+    types::{
+        Exploring, GameConfig, GameState, HintCircle, HintPressed, InfoColumn, Paused, SpawnCity,
+        ValidatedGuess,
+    },
+};
+
+/// Hints on the city to find, each lowering the points of the question.
+pub struct HintPlugin;
+
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_hint_text).add_systems(
+            Update,
+            (
+                give_hint.run_if(on_message::<HintPressed>),
+                clear_hints.run_if(on_message::<ValidatedGuess>.or(on_message::<SpawnCity>)),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Hints given so far, one per line.
+#[derive(Component)]
+struct HintText;
+
+pub(crate) fn setup_hint_text(mut commands: Commands, column: Single<Entity, With<InfoColumn>>) {
+    commands.spawn((
+        Text::default(),
+        TextColor(Color::BLACK),
+        HintText,
+        ChildOf(*column),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn give_hint(
+    mut commands: Commands,
+    mut pressed: MessageReader<HintPressed>,
+    mut quiz: ResMut<Quiz>,
+    language: Res<Language>,
+    game_state: Res<GameState>,
+    paused: Res<Paused>,
+    exploring: Res<Exploring>,
+    config: Res<GameConfig>,
+    mut text: Query<&mut Text, With<HintText>>,
+) {
+    pressed.clear();
+    // The province would give the region away
+    if paused.0 || exploring.0 || config.mode.is_region_quiz() || *game_state != GameState::Guess {
+        return;
+    }
+    let Some(hint) = quiz.hint() else {
+        return;
+    };
+    info!("Hint {}: {hint:?}", quiz.hints());
+    let strings = language.strings();
+    let line = match hint {
+        Hint::Province(name) => format!("{}{name}", strings.province),
+        Hint::Region(region) => format!("{}{}", strings.region, strings.region(region)),
+        Hint::Circle { center, radius } => {
+            commands.spawn(HintCircle {
+                center: Vec2::new(center.x, center.y),
+                radius,
+            });
+            strings.in_circle.to_string()
+        }
+    };
+    for mut text in &mut text {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&line);
+    }
+}
+
+fn clear_hints(
+    mut commands: Commands,
+    mut text: Query<&mut Text, With<HintText>>,
+    circles: Query<Entity, With<HintCircle>>,
+) {
+    for mut text in &mut text {
+        text.clear();
+    }
+    for entity in &circles {
+        commands.entity(entity).despawn();
+    }
+}
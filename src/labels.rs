@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use crate::{
+    core::{LabelBox, LabelRequest, Point, place_labels},
+    types::{MapLabel, MapSize},
+};
+
+/// Screen distance between a dot and its label, in pixels.
+const LABEL_GAP: f32 = 4.;
+/// Width of a character relative to the font size, for a monospace font.
+const CHAR_WIDTH: f32 = 0.6;
+/// Height of a line relative to the font size.
+const LINE_HEIGHT: f32 = 1.2;
+/// Zoom ratio from the last placement past which labels are placed again
+/// while the camera moves, before they get too small or large on screen.
+const ZOOM_STEP: f32 = 1.5;
+
+/// Places the [`MapLabel`]s around their dots, hiding the ones without room.
+pub struct LabelPlugin;
+
+impl Plugin for LabelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, place_map_labels);
+    }
+}
+
+/// Inputs of the last placement, which only changes with them.
+#[derive(Default, PartialEq)]
+struct Layout {
+    view: Option<(Vec2, f32)>,
+    labels: Vec<(Entity, LabelRequest)>,
+}
+
+/// Last placement and the camera view of the previous frame.
+#[derive(Default)]
+struct LabelState {
+    placed: Layout,
+    previous_view: Option<(Vec2, f32)>,
+}
+
+/// Labels keep their size on screen while zooming, so that zooming in makes
+/// room for more of them. They are placed again once the camera settles, or
+/// when the zoom changes too much while it moves.
+fn place_map_labels(
+    camera: Query<(&Transform, &Projection), With<Camera2d>>,
+    mut labels: Query<
+        (
+            Entity,
+            &MapLabel,
+            &ChildOf,
+            &Text2d,
+            &TextFont,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<Camera2d>,
+    >,
+    anchors: Query<(&GlobalTransform, &InheritedVisibility)>,
+    map: Res<MapSize>,
+    mut state: Local<LabelState>,
+) {
+    let Ok((camera, Projection::Orthographic(projection))) = camera.single() else {
+        return;
+    };
+    let scale = projection.scale;
+    let view = (camera.translation.truncate(), scale);
+    let moving = state.previous_view.replace(view) != Some(view);
+    let placed_scale = state.placed.view.map_or(scale, |(_, s)| s);
+    let ratio = scale.max(placed_scale) / scale.min(placed_scale);
+    if moving && ratio < ZOOM_STEP {
+        return;
+    }
+    let mut layout = Layout {
+        view: Some(view),
+        labels: Vec::new(),
+    };
+    for (entity, label, child_of, text, font, _, _) in &labels {
+        let Ok((anchor, visible)) = anchors.get(child_of.parent()) else {
+            continue;
+        };
+        if !visible.get() {
+            continue;
+        }
+        let anchor = anchor.translation().truncate();
+        let chars = text.chars().count() as f32;
+        let size = Vec2::new(chars * CHAR_WIDTH, LINE_HEIGHT) * font.font_size * scale;
+        layout.labels.push((
+            entity,
+            LabelRequest {
+                anchor: anchor.into(),
+                size: size.into(),
+                importance: label.importance,
+            },
+        ));
+    }
+    if layout == state.placed {
+        return;
+    }
+
+    let requests: Vec<LabelRequest> = layout.labels.iter().map(|(_, r)| *r).collect();
+    let bounds = LabelBox::from_center(Point::default(), map.0.into());
+    let centers = place_labels(&requests, bounds, LABEL_GAP * scale);
+    for (&(entity, request), center) in layout.labels.iter().zip(centers) {
+        let Ok((_, _, child_of, _, _, mut transform, mut vis)) = labels.get_mut(entity) else {
+            continue;
+        };
+        let Some(center) = center else {
+            *vis = Visibility::Hidden;
+            continue;
+        };
+        // Undo the scale of the dot, such as a highlighted one
+        let parent_scale = anchors
+            .get(child_of.parent())
+            .map_or(1., |(anchor, _)| anchor.scale().x);
+        let offset = Vec2::new(center.x - request.anchor.x, center.y - request.anchor.y);
+        transform.translation = (offset / parent_scale).extend(0.1);
+        transform.scale = Vec3::splat(scale / parent_scale);
+        *vis = Visibility::Inherited;
+    }
+    state.placed = layout;
+}
@@ -1,27 +1,72 @@
+#[cfg(feature = "core")]
+pub mod core;
+#[cfg(feature = "core")]
+pub use crate::core::{GeoError, Language, UiStrings, load_database};
+
+#[cfg(feature = "gui")]
 pub mod camera;
+#[cfg(feature = "gui")]
 use bevy::app::{PluginGroup, PluginGroupBuilder};
+#[cfg(feature = "gui")]
 pub use camera::MapPlugin;
 
-mod loader;
-pub use loader::*;
-
+#[cfg(feature = "gui")]
 pub mod types;
 
+#[cfg(feature = "gui")]
 mod assets;
+#[cfg(feature = "gui")]
 pub use assets::AssetsPlugin;
 
+#[cfg(feature = "gui")]
+mod controls;
+#[cfg(feature = "gui")]
+pub use controls::ControlsPlugin;
+
+#[cfg(feature = "gui")]
 mod game;
-pub use game::GamePlugin;
+#[cfg(feature = "gui")]
+pub use game::{GamePlugin, InitGamePlugin};
+
+#[cfg(feature = "gui")]
+mod reveal;
+#[cfg(feature = "gui")]
+pub use reveal::RevealPlugin;
+
+#[cfg(feature = "gui")]
+mod cursor;
+
+#[cfg(feature = "gui")]
+mod debug;
+#[cfg(feature = "gui")]
+pub use debug::DebugPlugin;
+
+#[cfg(feature = "gui")]
+mod explorer;
+
+#[cfg(feature = "gui")]
+mod hints;
+
+#[cfg(feature = "gui")]
+mod labels;
+
+#[cfg(feature = "gui")]
+mod region_quiz;
 
-use crate::game::InitGamePlugin;
+#[cfg(feature = "gui")]
+mod study;
 
+#[cfg(feature = "gui")]
 pub struct SetupPlugin;
 
+#[cfg(feature = "gui")]
 impl PluginGroup for SetupPlugin {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(AssetsPlugin)
             .add(MapPlugin)
             .add(InitGamePlugin)
+            .add(RevealPlugin)
+            .add(DebugPlugin)
     }
 }
@@ -1,9 +1,121 @@
+use std::path::{Path, PathBuf};
+
 use bevy::prelude::*;
+use clap::{Parser, builder::RangedU64ValueParser};
+
+use geo_quizz::{
+    GamePlugin, Language, SetupPlugin,
+    core::{Difficulty, NameKeys, ROUND_LENGTH, json_file},
+    types::{DebugOverlay, GameConfig, GameMode, OutsideGuesses, Profile},
+};
+
+/// Find Belgian cities on the map.
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Cli {
+    /// City database to draw the questions from: built by `geoquizz-data`,
+    /// GeoJSON points or CSV with name, lat and lon columns.
+    #[arg(long, default_value = "database/belgium_cities.json")]
+    dataset: PathBuf,
+    /// Property or column holding the city names in GeoJSON and CSV datasets.
+    #[arg(long, default_value = "name")]
+    name_field: String,
+    /// Map to draw, relative to the assets folder or absolute.
+    #[arg(long, default_value = "belgium_map.svg")]
+    map: PathBuf,
+    /// Seed of the city draw, to replay the same rounds.
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Number of cities per round.
+    #[arg(long, default_value_t = ROUND_LENGTH, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    rounds: usize,
+    /// Cities asked: all, easy (capitals and cities), medium (towns) or
+    /// hard (villages). All of them if the dataset has none of the kind.
+    #[arg(long, default_value = "all", value_parser = parse_difficulty)]
+    difficulty: Difficulty,
+    /// Game mode: locate, explore to browse the cities first, or regions
+    /// and regions-de to name the region of the cities.
+    #[arg(long, default_value = "locate", value_parser = parse_mode)]
+    mode: GameMode,
+    /// Clicks outside of the country: snap them to the border, or flag them
+    /// until moved inside.
+    #[arg(long, default_value = "snap", value_parser = parse_outside)]
+    outside: OutsideGuesses,
+    /// Language of names and texts: fr, nl, de, en or local.
+    #[arg(long, default_value = "fr", value_parser = parse_language)]
+    lang: Language,
+    /// Player name, each player having their own history in `saves/`.
+    #[arg(long, default_value = "default", value_parser = parse_profile)]
+    profile: String,
+    /// Study set to play, as saved by `geoquizz-data study`.
+    #[arg(long)]
+    study: Option<String>,
+    /// Start with the debug overlay, otherwise toggled with F3.
+    #[arg(long)]
+    debug_overlay: bool,
+}
 
-use geo_quizz::{GamePlugin, SetupPlugin};
+fn parse_mode(arg: &str) -> Result<GameMode, String> {
+    GameMode::from_name(arg).ok_or_else(|| {
+        let names: Vec<_> = GameMode::ALL.iter().map(|m| m.name()).collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+fn parse_outside(arg: &str) -> Result<OutsideGuesses, String> {
+    OutsideGuesses::from_name(arg).ok_or_else(|| {
+        let names: Vec<_> = OutsideGuesses::ALL.iter().map(|o| o.name()).collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+fn parse_difficulty(arg: &str) -> Result<Difficulty, String> {
+    Difficulty::from_name(arg).ok_or_else(|| {
+        let names: Vec<_> = Difficulty::ALL.iter().map(|d| d.name()).collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+fn parse_profile(arg: &str) -> Result<String, String> {
+    json_file(Path::new("saves"), arg)
+        .map(|_| arg.to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn parse_language(arg: &str) -> Result<Language, String> {
+    Language::from_code(arg).ok_or_else(|| {
+        let codes: Vec<_> = Language::ALL.iter().map(|l| l.code()).collect();
+        format!("expected one of {}", codes.join(", "))
+    })
+}
 
 fn main() {
+    let cli = Cli::parse();
+    let config = GameConfig {
+        dataset: cli.dataset,
+        name_keys: NameKeys {
+            default: cli.name_field,
+            ..Default::default()
+        },
+        map: cli.map,
+        seed: cli.seed,
+        round_length: cli.rounds,
+        difficulty: cli.difficulty,
+        mode: cli.mode,
+        outside_guesses: cli.outside,
+        saves: Some("saves".into()),
+        study_set: cli.study,
+    };
+    let dataset = config.load_dataset().unwrap_or_else(|e| {
+        eprintln!("{}: {e}", config.dataset.display());
+        std::process::exit(1);
+    });
     App::new()
+        .insert_resource(config)
+        .insert_resource(dataset)
+        .insert_resource(cli.lang)
+        .insert_resource(Profile(cli.profile))
+        .insert_resource(DebugOverlay(cli.debug_overlay))
         .add_plugins(SetupPlugin)
         .add_plugins(GamePlugin)
         .run();
@@ -0,0 +1,214 @@
+use bevy::{color::palettes::basic::GREEN, color::palettes::basic::RED, prelude::*};
+
+use crate::{
+    assets::{DEFAULT_BG, DEFAULT_BORDER},
+    core::{Language, Quiz, Region},
+    types::{
+        ActionButton, ConfirmPressed, Exploring, GameConfig, GameState, GuessType, Paused,
+        RegionPicked, SpawnCity,
+    },
+};
+
+/// Time the answer stays shown before the next city, in seconds.
+const NEXT_DELAY: f32 = 0.8;
+
+/// Keys picking the regions, in the order of their buttons.
+const REGION_KEYS: [KeyCode; 4] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+];
+
+/// Fast drill on the regions: a city name, one button per region, and the
+/// next city as soon as the answer is shown.
+pub struct RegionQuizPlugin;
+
+impl Plugin for RegionQuizPlugin {
+    fn build(&self, app: &mut App) {
+        let region_quiz = |config: Res<GameConfig>| config.mode.is_region_quiz();
+        // After the quiz is inserted, and the buttons spawned
+        app.add_systems(
+            PostStartup,
+            (spawn_region_buttons, hide_hint_button).run_if(region_quiz),
+        )
+        .add_systems(
+            Update,
+            (
+                reset_region_buttons.run_if(on_message::<SpawnCity>),
+                (press_region_button, region_keys),
+                answer_region.run_if(on_message::<RegionPicked>),
+                next_after_answer,
+                update_region_labels.run_if(resource_changed::<Language>),
+            )
+                .chain()
+                .run_if(region_quiz),
+        );
+    }
+}
+
+#[derive(Component)]
+struct RegionButton(Region);
+
+#[derive(Component)]
+struct RegionLabel(Region);
+
+fn spawn_region_buttons(mut commands: Commands, quiz: Res<Quiz>, language: Res<Language>) {
+    let strings = language.strings();
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            bottom: Val::Percent(10.),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(10.),
+            ..default()
+        })
+        .with_children(|row| {
+            for &region in quiz.regions() {
+                row.spawn((
+                    Button,
+                    RegionButton(region),
+                    Node {
+                        width: Val::Px(180.),
+                        height: Val::Px(75.),
+                        border: UiRect::all(Val::Px(5.)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BorderColor::all(DEFAULT_BORDER),
+                    BorderRadius::MAX,
+                    BackgroundColor(DEFAULT_BG),
+                    children![(
+                        Text::new(strings.region(region)),
+                        TextFont {
+                            font_size: 18.,
+                            ..default()
+                        },
+                        TextLayout::new_with_justify(Justify::Center),
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                        RegionLabel(region),
+                    )],
+                ));
+            }
+        });
+}
+
+/// The province hint would give the region away.
+fn hide_hint_button(mut buttons: Query<(&ActionButton, &mut Node)>) {
+    for (action, mut node) in &mut buttons {
+        if *action == ActionButton::Hint {
+            node.display = Display::None;
+        }
+    }
+}
+
+fn press_region_button(
+    buttons: Query<(&Interaction, &RegionButton), Changed<Interaction>>,
+    mut picked: MessageWriter<RegionPicked>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction == Interaction::Pressed {
+            picked.write(RegionPicked(button.0));
+        }
+    }
+}
+
+/// Digit keys pick the regions, in the order of the buttons.
+fn region_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    quiz: Res<Quiz>,
+    paused: Res<Paused>,
+    exploring: Res<Exploring>,
+    mut picked: MessageWriter<RegionPicked>,
+) {
+    // Digits select the study set while paused
+    if paused.0 || exploring.0 {
+        return;
+    }
+    for (key, &region) in REGION_KEYS.iter().zip(quiz.regions()) {
+        if keys.just_pressed(*key) {
+            picked.write(RegionPicked(region));
+        }
+    }
+}
+
+/// Answer with the region picked right away, showing the right one on the
+/// buttons.
+#[allow(clippy::too_many_arguments)]
+fn answer_region(
+    mut commands: Commands,
+    mut picked: MessageReader<RegionPicked>,
+    quiz: Res<Quiz>,
+    game_state: Res<GameState>,
+    paused: Res<Paused>,
+    exploring: Res<Exploring>,
+    guesses: Query<Entity, With<GuessType>>,
+    mut buttons: Query<(&RegionButton, &mut BackgroundColor)>,
+    mut confirm_event: MessageWriter<ConfirmPressed>,
+) {
+    // Only the first pick counts
+    let Some(RegionPicked(region)) = picked.read().next().copied() else {
+        return;
+    };
+    picked.clear();
+    if paused.0 || exploring.0 || *game_state != GameState::Guess {
+        return;
+    }
+    for entity in &guesses {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn(GuessType::Region(region));
+    confirm_event.write(ConfirmPressed);
+
+    let expected = quiz.current().and_then(|city| quiz.region_of(city));
+    for (button, mut bg_color) in &mut buttons {
+        if Some(button.0) == expected {
+            *bg_color = BackgroundColor(Color::from(GREEN));
+        } else if button.0 == region {
+            *bg_color = BackgroundColor(Color::from(RED));
+        }
+    }
+}
+
+/// Move to the next city once the answer has been shown for a moment.
+fn next_after_answer(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    paused: Res<Paused>,
+    mut timer: Local<Option<Timer>>,
+    mut confirm_event: MessageWriter<ConfirmPressed>,
+) {
+    if *game_state != GameState::Standby {
+        *timer = None;
+        return;
+    }
+    if paused.0 {
+        return;
+    }
+    let timer = timer.get_or_insert_with(|| Timer::from_seconds(NEXT_DELAY, TimerMode::Once));
+    if timer.tick(time.delta()).just_finished() {
+        confirm_event.write(ConfirmPressed);
+    }
+}
+
+fn reset_region_buttons(
+    mut commands: Commands,
+    guesses: Query<Entity, With<GuessType>>,
+    mut buttons: Query<&mut BackgroundColor, With<RegionButton>>,
+) {
+    // Left over from the previous city, it would answer the new one
+    for entity in &guesses {
+        commands.entity(entity).despawn();
+    }
+    for mut bg_color in &mut buttons {
+        *bg_color = BackgroundColor(DEFAULT_BG);
+    }
+}
+
+fn update_region_labels(language: Res<Language>, mut labels: Query<(&mut Text, &RegionLabel)>) {
+    for (mut text, label) in &mut labels {
+        **text = language.strings().region(label.0).to_string();
+    }
+}
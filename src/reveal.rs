@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+use crate::{
+    core::{Language, Quiz},
+    types::{
+        GuessAssets, GuessEvaluated, GuessType, HintCircle, InfoColumn, OutsideCountry, SpawnCity,
+    },
+};
+
+/// Time for the distance line to reach the answer, in seconds.
+const LINE_DURATION: f32 = 0.8;
+
+pub struct RevealPlugin;
+
+impl Plugin for RevealPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                clear_reveal.run_if(on_message::<SpawnCity>),
+                start_reveal.run_if(on_message::<GuessEvaluated>),
+                draw_reveal,
+                draw_hint_circles,
+                draw_outline_warning,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Line from the guess to the answer, labelled with the distance.
+#[derive(Component)]
+struct Reveal {
+    guess: Vec2,
+    answer: Vec2,
+    points: u32,
+    started: f32,
+}
+
+/// Where the guess actually landed, shown with the reveal.
+#[derive(Component)]
+struct Surroundings;
+
+/// Red for no point, green for every point.
+fn points_color(points: u32, max: u32) -> Color {
+    let ratio = points as f32 / max.max(1) as f32;
+    Color::hsl(120. * ratio, 0.8, 0.45)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_reveal(
+    mut commands: Commands,
+    mut evaluated: MessageReader<GuessEvaluated>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    quiz: Res<Quiz>,
+    language: Res<Language>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut marker: Query<&mut MeshMaterial2d<ColorMaterial>, With<GuessType>>,
+    column: Single<Entity, With<InfoColumn>>,
+) {
+    let path: PathBuf = ["fonts", "FiraMono-Medium.ttf"].iter().collect();
+    let font: Handle<Font> = asset_server.load(path);
+    let curve = quiz.curve();
+    for ev in evaluated.read() {
+        let color = points_color(ev.points, curve.max);
+        for mut material in &mut marker {
+            material.0 = materials.add(color);
+        }
+        commands.spawn((
+            Reveal {
+                guess: ev.guess,
+                answer: ev.answer,
+                points: ev.points,
+                started: time.elapsed_secs(),
+            },
+            Text2d::new(if ev.province_bonus {
+                format!(
+                    "{:.1} km (+{})",
+                    ev.distance_km,
+                    language.strings().province_bonus
+                )
+            } else {
+                format!("{:.1} km", ev.distance_km)
+            }),
+            TextFont {
+                font: font.clone(),
+                font_size: 15.,
+                ..default()
+            },
+            TextColor(Color::BLACK),
+            Transform::from_translation(ev.guess.midpoint(ev.answer).extend(0.3)),
+            // Shown once the line is fully drawn
+            Visibility::Hidden,
+        ));
+        if let Some(nearest) = &ev.nearest {
+            let mut text = format!(
+                "{}{}",
+                language.strings().clicked_near,
+                nearest.get(*language)
+            );
+            if let Some(province) = &ev.province {
+                text.push_str(&format!(" ({province})"));
+            }
+            commands.spawn((
+                Text::new(text),
+                TextColor(Color::BLACK),
+                Surroundings,
+                ChildOf(*column),
+            ));
+        }
+    }
+}
+
+fn draw_reveal(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    quiz: Res<Quiz>,
+    mut reveals: Query<(&Reveal, &mut Visibility)>,
+) {
+    let curve = quiz.curve();
+    for (reveal, mut vis) in &mut reveals {
+        let progress = ((time.elapsed_secs() - reveal.started) / LINE_DURATION).clamp(0., 1.);
+        let end = reveal.guess.lerp(reveal.answer, progress);
+        gizmos.line_2d(reveal.guess, end, points_color(reveal.points, curve.max));
+        for (points, radius) in curve.bands() {
+            gizmos.circle_2d(
+                Isometry2d::from_translation(reveal.answer),
+                radius * progress,
+                points_color(points, curve.max).with_alpha(0.6),
+            );
+        }
+        if progress >= 1. {
+            *vis = Visibility::Visible;
+        }
+    }
+}
+
+fn draw_hint_circles(mut gizmos: Gizmos, circles: Query<&HintCircle>) {
+    for circle in &circles {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(circle.center),
+            circle.radius,
+            Color::srgb(0.1, 0.3, 0.8),
+        );
+    }
+}
+
+/// Border of the country, drawn while the guess is outside.
+fn draw_outline_warning(
+    mut gizmos: Gizmos,
+    quiz: Res<Quiz>,
+    outside: Query<(), With<OutsideCountry>>,
+) {
+    if outside.is_empty() {
+        return;
+    }
+    for (a, b) in &quiz.outline().segments {
+        gizmos.line_2d(
+            Vec2::new(a.x, a.y),
+            Vec2::new(b.x, b.y),
+            Color::srgb(0.8, 0.1, 0.1),
+        );
+    }
+}
+
+fn clear_reveal(
+    mut commands: Commands,
+    reveals: Query<Entity, Or<(With<Reveal>, With<Surroundings>)>>,
+    guess_assets: Res<GuessAssets>,
+    mut marker: Query<&mut MeshMaterial2d<ColorMaterial>, With<GuessType>>,
+) {
+    for entity in &reveals {
+        commands.entity(entity).despawn();
+    }
+    for mut material in &mut marker {
+        material.0 = guess_assets.material.clone();
+    }
+}
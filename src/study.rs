@@ -0,0 +1,107 @@
+use bevy::{
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
+};
+
+use crate::{
+    core::{Language, Quiz},
+    types::{GameState, Paused, Score, SpawnCity, StudyMenu, StudySets},
+};
+
+/// Keys selecting, while paused, all cities then each study set in turn.
+const SET_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Choice of the study set from the pause menu.
+pub struct StudyPlugin;
+
+impl Plugin for StudyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StudySets>().add_systems(
+            Update,
+            (
+                select_study_set,
+                update_study_menu
+                    .run_if(resource_changed::<StudySets>.or(resource_changed::<Language>)),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Digit keys pick the set while paused, starting a new round drawn from it.
+fn select_study_set(
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut sets: ResMut<StudySets>,
+    mut paused: ResMut<Paused>,
+    mut quiz: ResMut<Quiz>,
+    mut score: ResMut<Score>,
+    mut game_state: ResMut<GameState>,
+    mut spawn_event: MessageWriter<SpawnCity>,
+) {
+    if !paused.0 {
+        keyboard.clear();
+        return;
+    }
+    let Some(index) = keyboard
+        .read()
+        .filter(|ev| ev.state == ButtonState::Pressed && !ev.repeat)
+        .filter_map(|ev| SET_KEYS.iter().position(|key| *key == ev.key_code))
+        .last()
+    else {
+        return;
+    };
+    // 1 is every city, the sets follow
+    let active = index.checked_sub(1);
+    if active.is_some_and(|i| i >= sets.sets.len()) {
+        return;
+    }
+
+    let pool = active.map(|i| sets.sets[i].pool());
+    let name = active.map_or("all cities", |i| sets.sets[i].name.as_str());
+    if let Err(e) = quiz.set_pool(pool).and_then(|()| quiz.start_round()) {
+        warn!("Not studying {name}: {e}");
+        let previous = sets.active.map(|i| sets.sets[i].pool());
+        quiz.set_pool(previous).ok();
+        return;
+    }
+    info!("Studying {name}");
+    sets.active = active;
+    *score = quiz.score();
+    // Not `Guess`, so that the current city is despawned
+    *game_state = GameState::Standby;
+    spawn_event.write(SpawnCity);
+    paused.0 = false;
+}
+
+fn update_study_menu(
+    sets: Res<StudySets>,
+    language: Res<Language>,
+    mut text: Query<&mut Text, With<StudyMenu>>,
+) {
+    let strings = language.strings();
+    let names = std::iter::once(strings.all_cities)
+        .chain(sets.sets.iter().map(|set| set.name.as_str()))
+        .take(SET_KEYS.len());
+    let mut menu = format!("{}\n", strings.study_sets);
+    for (i, name) in names.enumerate() {
+        let marker = if sets.active == i.checked_sub(1) {
+            '>'
+        } else {
+            ' '
+        };
+        menu.push_str(&format!("\n{marker} {} {name}", i + 1));
+    }
+    for mut text in &mut text {
+        **text = menu.clone();
+    }
+}
@@ -1,19 +1,16 @@
 use std::path::PathBuf;
 
-use crate::loader::load_database;
 use bevy::{
     ecs::{bundle::Bundle, component::Component, resource::Resource},
     math::Vec2,
     prelude::*,
 };
-use rand::seq::IteratorRandom;
-use thiserror::Error;
 
-#[derive(Debug, Error)]
-pub enum GeoError {
-    #[error("Error while loading database {0}")]
-    DataLoading(String),
-}
+pub use crate::core::{CityId, CityNames, GeoError, Score, ScoringCurve};
+use crate::core::{
+    CityRecord, DatasetOptions, Difficulty, Language, NameKeys, NoRepeats, Point, ProvinceMap,
+    Region, SelectionStrategy, Spread, StudySet, json_file, load_database_with, validate_database,
+};
 
 #[derive(Component, Debug, Clone)]
 pub struct Location(pub Vec2);
@@ -24,53 +21,42 @@ impl From<Vec2> for Location {
     }
 }
 
+impl From<Point> for Location {
+    fn from(value: Point) -> Self {
+        Self(Vec2::new(value.x, value.y))
+    }
+}
+
+impl From<Vec2> for Point {
+    fn from(value: Vec2) -> Self {
+        Point::new(value.x, value.y)
+    }
+}
+
 #[derive(Component, Debug, Clone)]
 pub struct City;
 
 #[derive(Bundle, Debug, Clone)]
 pub struct BundleCity {
     pub city: City,
+    pub id: CityId,
     pub name: Name,
+    pub names: CityNames,
     pub loc: Location,
 }
 
-#[derive(Debug, Resource)]
-pub struct GuessSet {
-    all_cities: Vec<BundleCity>,
-    pub to_guess: Vec<BundleCity>,
-}
-
-impl Default for GuessSet {
-    fn default() -> Self {
-        let path: PathBuf = ["database", "belgium_cities.json"].iter().collect();
-        let all_cities = load_database(path).expect("Should exists");
-
-        let to_guess = Self::pick_randomly(&all_cities);
+impl BundleCity {
+    pub fn new(record: &CityRecord, language: Language) -> Self {
         Self {
-            all_cities,
-            to_guess,
+            city: City,
+            id: record.id,
+            name: record.name(language).to_string().into(),
+            names: record.names.clone(),
+            loc: record.loc.into(),
         }
     }
 }
 
-impl GuessSet {
-    pub fn load_next(&mut self) {
-        let next = Self::pick_randomly(&self.all_cities);
-        self.to_guess = next;
-    }
-
-    fn pick_randomly(all_cities: &[BundleCity]) -> Vec<BundleCity> {
-        let mut rng = rand::rng();
-
-        all_cities
-            .iter()
-            .choose_multiple(&mut rng, 10)
-            .into_iter()
-            .cloned()
-            .collect()
-    }
-}
-
 #[derive(Resource)]
 pub struct GuessAssets {
     pub mesh: Handle<Mesh>,
@@ -81,29 +67,287 @@ pub struct GuessAssets {
 pub struct CityAssets {
     pub mesh: Handle<Mesh>,
     pub material: Handle<ColorMaterial>,
+    pub font: Handle<Font>,
 }
 
-#[derive(Resource, Eq, PartialEq)]
+#[derive(Resource, Debug, Clone, Copy, Eq, PartialEq)]
 pub enum GameState {
     Guess,
     Standby,
+    /// Every city of the round has been guessed.
+    RoundOver,
 }
 
+/// Whether the game is paused, toggled with `Esc`.
 #[derive(Default, Resource)]
-pub struct Score {
-    pub total: u32,
-    pub max: u32,
+pub struct Paused(pub bool);
+
+/// Whether every city is shown to be studied instead of guessed, toggled
+/// with `Tab`. The quiz waits meanwhile.
+#[derive(Default, Resource)]
+pub struct Exploring(pub bool);
+
+/// Smallest distance between two cities of a round to locate.
+const MIN_CITY_DISTANCE_KM: f32 = 15.;
+
+/// How the game is played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameMode {
+    /// Click where the named city is.
+    #[default]
+    Locate,
+    /// Browse every city before playing, see [`Exploring`].
+    Explore,
+    /// Tell the region of the named city with a button, the next city
+    /// coming right after.
+    Regions,
+    /// Same as [`GameMode::Regions`], the German-speaking community being
+    /// told apart from Wallonia.
+    RegionsGerman,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Locate,
+        GameMode::Explore,
+        GameMode::Regions,
+        GameMode::RegionsGerman,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Locate => "locate",
+            GameMode::Explore => "explore",
+            GameMode::Regions => "regions",
+            GameMode::RegionsGerman => "regions-de",
+        }
+    }
+
+    /// Answered with region buttons instead of a click on the map.
+    pub fn is_region_quiz(self) -> bool {
+        matches!(self, GameMode::Regions | GameMode::RegionsGerman)
+    }
+
+    /// How the cities of a round are drawn: never twice before the whole
+    /// pool has been asked, spread over the provinces, and apart from each
+    /// other when they are to be located.
+    pub fn selection(self, provinces: &ProvinceMap) -> Box<dyn SelectionStrategy> {
+        let min_distance_km = match self {
+            GameMode::Locate | GameMode::Explore => MIN_CITY_DISTANCE_KM,
+            // Neighbours are as good a drill of the regions as any
+            GameMode::Regions | GameMode::RegionsGerman => 0.,
+        };
+        Box::new(NoRepeats::new(
+            Spread::new(min_distance_km).with_provinces(provinces.clone()),
+        ))
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// What to do with a guess outside of the country.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutsideGuesses {
+    /// Move it to the nearest point of the border.
+    #[default]
+    Snap,
+    /// Leave it, with a warning, until it is moved inside.
+    Flag,
+}
+
+impl OutsideGuesses {
+    pub const ALL: [OutsideGuesses; 2] = [OutsideGuesses::Snap, OutsideGuesses::Flag];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OutsideGuesses::Snap => "snap",
+            OutsideGuesses::Flag => "flag",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|o| o.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Launch settings, read once when the game starts.
+#[derive(Resource, Debug, Clone)]
+pub struct GameConfig {
+    /// City database, in any [`DatasetFormat`](crate::core::DatasetFormat).
+    pub dataset: PathBuf,
+    /// Where the names are in GeoJSON and CSV datasets.
+    pub name_keys: NameKeys,
+    /// Map drawn by the game, relative to the `assets` folder.
+    pub map: PathBuf,
+    /// Draw the same cities at every launch.
+    pub seed: Option<u64>,
+    pub round_length: usize,
+    pub difficulty: Difficulty,
+    pub mode: GameMode,
+    pub outside_guesses: OutsideGuesses,
+    /// Folder of the player histories, not saved if `None`.
+    pub saves: Option<PathBuf>,
+    /// Study set played from the start, all cities if `None`.
+    pub study_set: Option<String>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            dataset: ["database", "belgium_cities.json"].iter().collect(),
+            name_keys: NameKeys::default(),
+            map: "belgium_map.svg".into(),
+            seed: None,
+            round_length: crate::core::ROUND_LENGTH,
+            difficulty: Difficulty::default(),
+            mode: GameMode::default(),
+            outside_guesses: OutsideGuesses::default(),
+            saves: None,
+            study_set: None,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Read [`GameConfig::dataset`], failing on the errors found by the
+    /// validation. Checks against the map are left to `geoquizz-data
+    /// validate`.
+    pub fn load_dataset(&self) -> Result<Dataset, GeoError> {
+        let options = DatasetOptions {
+            format: None,
+            names: self.name_keys.clone(),
+        };
+        let cities = load_database_with(&self.dataset, &options)?;
+        let report = validate_database(&cities, &ProvinceMap::default());
+        match report.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(Dataset(cities)),
+        }
+    }
+
+    /// Path of the map on disk, for reading it outside of the asset server.
+    pub fn map_file(&self) -> PathBuf {
+        PathBuf::from("assets").join(&self.map)
+    }
+
+    /// `None` without a saves folder, or if the profile cannot name a file
+    /// in it.
+    pub fn history_file(&self, profile: &Profile) -> Option<PathBuf> {
+        json_file(self.saves.as_ref()?, &profile.0).ok()
+    }
+
+    /// Folder of the sets saved by `geoquizz-data study`.
+    pub fn study_dir(&self) -> Option<PathBuf> {
+        Some(self.saves.as_ref()?.join("study"))
+    }
+}
+
+/// Cities of [`GameConfig::dataset`], loaded before the game starts so that
+/// a wrong dataset is reported rather than crashing it.
+#[derive(Resource, Debug, Default)]
+pub struct Dataset(pub Vec<CityRecord>);
+
+/// Name of the player, separating the saved history of several players.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct Profile(pub String);
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self("default".to_string())
+    }
+}
+
+/// Saved study sets, and the index of the one restricting the draw.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct StudySets {
+    pub sets: Vec<StudySet>,
+    pub active: Option<usize>,
+}
+
+/// Size of the map in world units, centered on the origin, read from the
+/// `--map` SVG.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct MapSize(pub Vec2);
+
+/// Whether debugging aids, such as the city to find, are drawn. Toggled
+/// with `F3`.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct DebugOverlay(pub bool);
+
+/// Outcome of a location guess, sent once it is evaluated.
+#[derive(Message, Debug, Clone)]
+pub struct GuessEvaluated {
+    pub city: CityId,
+    pub guess: Vec2,
+    pub answer: Vec2,
+    pub distance_km: f32,
+    pub points: u32,
+    /// City closest to the guess.
+    pub nearest: Option<CityNames>,
+    /// Province the guess fell in.
+    pub province: Option<String>,
+    /// The guess got the province bonus.
+    pub province_bonus: bool,
 }
 
 #[derive(Component, Debug)]
 pub enum GuessType {
     Name(String),
     Location(Vec2),
+    Region(Region),
 }
 
 #[derive(Component)]
 pub struct CityNameToGuess;
 
+/// Static interface text, refreshed when the [`Language`] changes.
+#[derive(Component, Clone, Copy)]
+pub enum UiLabel {
+    Find,
+    Score,
+    Paused,
+    Hint,
+    OutsideCountry,
+}
+
+/// What a button of the interface does.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionButton {
+    Confirm,
+    Hint,
+}
+
+#[derive(Component)]
+pub struct ConfirmLabel;
+
+#[derive(Component)]
+pub struct CityLabel;
+
+/// Column of texts at the top left, under the city to find, so that they
+/// do not overlap: hints, search and where the guess landed.
+#[derive(Component)]
+pub struct InfoColumn;
+
+/// Name drawn next to its parent dot, placed so that labels do not overlap.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MapLabel {
+    /// Labels of important cities are kept when there is no room for all.
+    pub importance: f32,
+}
+
+#[derive(Component)]
+pub struct PauseOverlay;
+
+/// List of the study sets, shown on the pause overlay.
+#[derive(Component)]
+pub struct StudyMenu;
+
 #[derive(Component)]
 pub struct WorldClickCatcher;
 
@@ -116,6 +360,33 @@ pub enum CityState {
 #[derive(Component)]
 pub struct ScoreText;
 
+/// Confirm button pressed, either with the mouse or the keyboard.
+#[derive(Message)]
+pub struct ConfirmPressed;
+
+/// Hint button pressed, either with the mouse or the keyboard.
+#[derive(Message)]
+pub struct HintPressed;
+
+/// Guess outside of the country, which cannot be confirmed.
+#[derive(Component)]
+pub struct OutsideCountry;
+
+/// Shown while the guess is [`OutsideCountry`].
+#[derive(Component)]
+pub struct OutsideWarning;
+
+/// Region picked in the region quiz, with its button or its digit key.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct RegionPicked(pub Region);
+
+/// Circle the city to find is in, given as a hint.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HintCircle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
 #[derive(Message)]
 pub struct ValidatedGuess;
 
@@ -0,0 +1,447 @@
+use std::time::Duration;
+
+use bevy::{
+    input::{
+        ButtonState, InputPlugin,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+use geo_quizz::{
+    GamePlugin, InitGamePlugin, Language,
+    core::{CityId, Difficulty, History, Quiz, StudySet, max_points_with_hints},
+    types::{
+        City, CityAssets, CityLabel, CityNames, ConfirmPressed, Exploring, GameConfig, GameMode,
+        GameState, GuessAssets, GuessType, HintPressed, InfoColumn, Location, OutsideCountry,
+        OutsideGuesses, Paused, Profile, RegionPicked, Score, StudySets,
+    },
+};
+
+const ROUND_LENGTH: u32 = 10;
+
+/// Game logic only: no window, no renderer, no asset loading.
+fn headless_app() -> App {
+    headless_app_with(GameConfig::default())
+}
+
+fn headless_app_with(config: GameConfig) -> App {
+    let mut app = App::new();
+    let dataset = config.load_dataset().unwrap();
+    app.add_plugins((MinimalPlugins, InputPlugin))
+        .insert_resource(config)
+        .insert_resource(dataset)
+        .insert_resource(GuessAssets {
+            mesh: Handle::default(),
+            material: Handle::default(),
+        })
+        .insert_resource(CityAssets {
+            mesh: Handle::default(),
+            material: Handle::default(),
+            font: Handle::default(),
+        })
+        .add_plugins((InitGamePlugin, GamePlugin));
+    // Startup, then the first city is spawned
+    app.update();
+    app
+}
+
+fn current_city(app: &mut App) -> (Entity, Vec2) {
+    let mut query = app
+        .world_mut()
+        .query_filtered::<(Entity, &Location), With<City>>();
+    let cities: Vec<_> = query
+        .iter(app.world())
+        .map(|(entity, loc)| (entity, loc.0))
+        .collect();
+    assert_eq!(cities.len(), 1, "exactly one city should be spawned");
+    cities[0]
+}
+
+fn place_guess(app: &mut App, pos: Vec2) {
+    let world = app.world_mut();
+    let mut existing = world.query_filtered::<Entity, With<GuessType>>();
+    let existing: Vec<_> = existing.iter(world).collect();
+    for entity in existing {
+        world.despawn(entity);
+    }
+    world.spawn((
+        Transform::from_translation(pos.extend(0.1)),
+        GuessType::Location(pos),
+    ));
+}
+
+fn press_confirm(app: &mut App) {
+    app.world_mut().write_message(ConfirmPressed);
+    app.update();
+}
+
+fn farthest_city(app: &App, from: Vec2) -> Vec2 {
+    app.world()
+        .resource::<Quiz>()
+        .guess_set()
+        .all_cities()
+        .iter()
+        .map(|city| Vec2::new(city.loc.x, city.loc.y))
+        .max_by(|a, b| a.distance(from).total_cmp(&b.distance(from)))
+        .unwrap()
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<GameState>()
+}
+
+fn score(app: &App) -> (u32, u32) {
+    let score = app.world().resource::<Score>();
+    (score.total, score.max)
+}
+
+#[test]
+fn full_round_of_perfect_guesses() {
+    let mut app = headless_app();
+    let mut seen = Vec::new();
+
+    for i in 0..ROUND_LENGTH {
+        assert_eq!(state(&app), GameState::Guess);
+        let (city, loc) = current_city(&mut app);
+        assert!(!seen.contains(&city), "a new city entity is spawned");
+        seen.push(city);
+
+        place_guess(&mut app, loc);
+        press_confirm(&mut app);
+        assert_eq!(state(&app), GameState::Standby);
+        assert_eq!(score(&app), (100 * (i + 1), 100 * (i + 1)));
+
+        press_confirm(&mut app);
+        if i + 1 < ROUND_LENGTH {
+            assert!(
+                app.world().get_entity(city).is_err(),
+                "previous city is despawned"
+            );
+        }
+    }
+
+    assert_eq!(state(&app), GameState::RoundOver);
+    assert_eq!(score(&app), (1000, 1000));
+
+    // Continue starts a fresh round
+    press_confirm(&mut app);
+    assert_eq!(state(&app), GameState::Guess);
+    assert_eq!(score(&app), (0, 0));
+    let (city, _) = current_city(&mut app);
+    assert!(!seen.contains(&city));
+}
+
+#[test]
+fn far_guess_gives_no_point() {
+    // Seeded so the first city sits at the edge of the country
+    let mut app = headless_app_with(GameConfig {
+        seed: Some(1),
+        ..Default::default()
+    });
+    let (city, loc) = current_city(&mut app);
+    let id = *app.world().get::<CityId>(city).unwrap();
+    // The city furthest away, as guesses outside of the country are moved
+    let far = farthest_city(&app, loc);
+    let curve = *app.world().resource::<Quiz>().curve();
+    assert!(far.distance(loc) > curve.tolerance + curve.range);
+
+    place_guess(&mut app, far);
+    press_confirm(&mut app);
+
+    assert_eq!(state(&app), GameState::Standby);
+    assert_eq!(score(&app), (0, 100));
+    let stats = app.world().resource::<History>().stats(id).copied();
+    assert_eq!(stats.map(|s| (s.asked, s.points)), Some((1, 0)));
+}
+
+#[test]
+fn hints_lower_the_points() {
+    let mut app = headless_app();
+    let (_, loc) = current_city(&mut app);
+
+    for _ in 0..2 {
+        app.world_mut().write_message(HintPressed);
+        app.update();
+    }
+    let hints = app.world().resource::<Quiz>().hints();
+    assert!(hints >= 2, "two hints given, some maybe skipped");
+
+    place_guess(&mut app, loc);
+    press_confirm(&mut app);
+    assert_eq!(score(&app), (max_points_with_hints(100, hints), 100));
+
+    // No hint outside of a question
+    app.world_mut().write_message(HintPressed);
+    app.update();
+    assert_eq!(app.world().resource::<Quiz>().hints(), hints);
+}
+
+#[test]
+fn confirm_without_guess_is_ignored() {
+    let mut app = headless_app();
+
+    press_confirm(&mut app);
+
+    assert_eq!(state(&app), GameState::Guess);
+    assert_eq!(score(&app), (0, 0));
+}
+
+#[test]
+fn guess_outside_the_country_is_snapped_to_the_border() {
+    let mut app = headless_app();
+
+    place_guess(&mut app, Vec2::new(2000., 0.));
+    app.update();
+
+    let mut guesses = app.world_mut().query::<&GuessType>();
+    let GuessType::Location(pos) = guesses.single(app.world()).unwrap() else {
+        panic!("a location guess is placed");
+    };
+    assert!(pos.x < 400., "moved onto the border: {pos}");
+    assert!(
+        app.world()
+            .resource::<Quiz>()
+            .accepts_location((*pos).into())
+    );
+    press_confirm(&mut app);
+    assert_eq!(state(&app), GameState::Standby);
+}
+
+#[test]
+fn flagged_guess_outside_the_country_cannot_be_confirmed() {
+    let config = GameConfig {
+        outside_guesses: OutsideGuesses::Flag,
+        ..Default::default()
+    };
+    let mut app = headless_app_with(config);
+
+    place_guess(&mut app, Vec2::new(2000., 0.));
+    press_confirm(&mut app);
+    assert_eq!(state(&app), GameState::Guess);
+    let mut flagged = app.world_mut().query::<&OutsideCountry>();
+    assert_eq!(flagged.iter(app.world()).count(), 1);
+
+    let (_, loc) = current_city(&mut app);
+    place_guess(&mut app, loc);
+    press_confirm(&mut app);
+    assert_eq!(state(&app), GameState::Standby);
+    assert_eq!(score(&app), (100, 100));
+}
+
+#[test]
+fn config_sets_round_length_and_seed() {
+    let config = GameConfig {
+        seed: Some(7),
+        round_length: 3,
+        ..Default::default()
+    };
+    let mut app = headless_app_with(config.clone());
+    let mut other = headless_app_with(config);
+    let (_, first) = current_city(&mut app);
+    assert_eq!(first, current_city(&mut other).1, "same seed, same city");
+
+    for _ in 0..3 {
+        let (_, loc) = current_city(&mut app);
+        place_guess(&mut app, loc);
+        press_confirm(&mut app);
+        press_confirm(&mut app);
+    }
+    assert_eq!(state(&app), GameState::RoundOver);
+    assert_eq!(score(&app), (300, 300));
+}
+
+#[test]
+fn empty_difficulty_falls_back_to_every_city() {
+    let dataset = std::env::temp_dir().join("geoquizz_towns_test.json");
+    std::fs::write(
+        &dataset,
+        r#"[
+            {"name:default": "Ath", "lat": 50.63, "lon": 3.78, "place": "town"},
+            {"name:default": "Mons", "lat": 50.45, "lon": 3.95, "place": "town"}
+        ]"#,
+    )
+    .unwrap();
+    let mut app = headless_app_with(GameConfig {
+        dataset,
+        difficulty: Difficulty::Hard,
+        ..Default::default()
+    });
+    current_city(&mut app);
+    assert_eq!(
+        app.world().resource::<Quiz>().guess_set().difficulty(),
+        Difficulty::All
+    );
+}
+
+#[test]
+fn study_set_selected_from_pause_menu() {
+    let mut app = headless_app();
+    let (city, _) = current_city(&mut app);
+    let id = *app.world().get::<CityId>(city).unwrap();
+    app.world_mut().resource_mut::<StudySets>().sets = vec![StudySet {
+        name: "exam".into(),
+        cities: vec![id],
+    }];
+    app.world_mut().resource_mut::<Paused>().0 = true;
+
+    app.world_mut().write_message(KeyboardInput {
+        key_code: KeyCode::Digit2,
+        logical_key: Key::Character("2".into()),
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+
+    assert!(!app.world().resource::<Paused>().0);
+    assert_eq!(app.world().resource::<StudySets>().active, Some(0));
+    let (new_city, _) = current_city(&mut app);
+    assert_ne!(new_city, city, "a new round has started");
+    assert_eq!(*app.world().get::<CityId>(new_city).unwrap(), id);
+    // The set holds a single city
+    assert_eq!(app.world().resource::<Quiz>().remaining(), 0);
+}
+
+#[test]
+fn explore_mode_shows_every_city_and_holds_the_quiz() {
+    let config = GameConfig {
+        mode: GameMode::Explore,
+        ..Default::default()
+    };
+    let mut app = headless_app_with(config);
+    assert!(app.world().resource::<Exploring>().0);
+    let cities = app
+        .world()
+        .resource::<Quiz>()
+        .guess_set()
+        .all_cities()
+        .len();
+    let mut ids = app.world_mut().query::<&CityId>();
+    // A dot per city, and the hidden city to guess
+    assert_eq!(ids.iter(app.world()).count(), cities + 1);
+
+    let (_, loc) = current_city(&mut app);
+    place_guess(&mut app, loc);
+    press_confirm(&mut app);
+    assert_eq!(state(&app), GameState::Guess);
+    assert_eq!(score(&app), (0, 0));
+}
+
+#[test]
+fn region_quiz_answers_with_a_button_and_moves_on() {
+    let config = GameConfig {
+        mode: GameMode::Regions,
+        ..Default::default()
+    };
+    let mut app = headless_app_with(config);
+    let (city, _) = current_city(&mut app);
+    let id = *app.world().get::<CityId>(city).unwrap();
+    let quiz = app.world().resource::<Quiz>();
+    let record = quiz.guess_set().all_cities().iter().find(|c| c.id == id);
+    let region = record.and_then(|c| quiz.region_of(c)).unwrap();
+
+    app.world_mut().write_message(RegionPicked(region));
+    app.update();
+    app.update();
+    assert_eq!(state(&app), GameState::Standby);
+    assert_eq!(score(&app), (100, 100));
+
+    // The next city comes without confirming. Virtual time steps are capped
+    // to a quarter of a second.
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(state(&app), GameState::Guess);
+    assert_ne!(current_city(&mut app).0, city);
+}
+
+#[test]
+fn consecutive_rounds_do_not_repeat_cities() {
+    let mut app = headless_app();
+    let mut asked = Vec::new();
+    for _ in 0..2 * ROUND_LENGTH {
+        let (city, loc) = current_city(&mut app);
+        let id = *app.world().get::<CityId>(city).unwrap();
+        assert!(!asked.contains(&id), "{id:?} asked twice");
+        asked.push(id);
+        place_guess(&mut app, loc);
+        press_confirm(&mut app);
+        press_confirm(&mut app);
+        if state(&app) == GameState::RoundOver {
+            press_confirm(&mut app);
+        }
+    }
+}
+
+#[test]
+fn history_file_stays_in_the_saves_folder() {
+    let config = GameConfig {
+        saves: Some("saves".into()),
+        ..Default::default()
+    };
+    let path = config.history_file(&Profile("jean.dupont".into()));
+    assert_eq!(path, Some("saves/jean.dupont.json".into()));
+    for profile in ["../x", "a/b"] {
+        assert_eq!(config.history_file(&Profile(profile.into())), None);
+    }
+}
+
+#[test]
+fn wrong_dataset_is_reported_before_the_game() {
+    let config = GameConfig {
+        dataset: "database/missing.json".into(),
+        ..Default::default()
+    };
+    assert!(config.load_dataset().is_err());
+}
+
+#[test]
+fn language_change_renames_each_city_label() {
+    let mut app = headless_app();
+    current_city(&mut app);
+    let names = CityNames {
+        default: "Antwerpen".into(),
+        fr: Some("Anvers".into()),
+        nl: None,
+        de: None,
+        en: None,
+    };
+    let world = app.world_mut();
+    let other = world.spawn((City, names, Name::new("Antwerpen"))).id();
+    world.spawn((Text2d::new("Antwerpen"), CityLabel, ChildOf(other)));
+    world.insert_resource(Language::Fr);
+    app.update();
+
+    let expected = app
+        .world()
+        .resource::<Quiz>()
+        .current()
+        .unwrap()
+        .name(Language::Fr)
+        .to_string();
+    let mut labels = app.world_mut().query_filtered::<&Text2d, With<CityLabel>>();
+    let mut texts: Vec<String> = labels.iter(app.world()).map(|t| t.0.clone()).collect();
+    texts.sort();
+    let mut wanted = vec!["Anvers".to_string(), expected];
+    wanted.sort();
+    assert_eq!(texts, wanted);
+}
+
+#[test]
+fn top_left_texts_share_one_column() {
+    let mut app = headless_app();
+    let mut column = app
+        .world_mut()
+        .query_filtered::<&Children, With<InfoColumn>>();
+    let texts = column.single(app.world()).unwrap();
+    // Hints, then the search of the explorer
+    assert_eq!(texts.len(), 2);
+    assert!(
+        texts
+            .iter()
+            .all(|text| app.world().get::<Text>(text).is_some())
+    );
+}